@@ -2,11 +2,28 @@
 
 #[ink::contract]
 mod erc20 {
+    use ink::prelude::{
+        string::String,
+        vec,
+        vec::Vec,
+    };
     use ink::storage::Mapping;
 
     /// A simple ERC-20 contract.
+    ///
+    /// # Storage layout
+    ///
+    /// The contract can be upgraded in place with `set_code_hash`, which keeps the
+    /// existing storage. Every field that is not a `Mapping` or `Lazy` is encoded
+    /// into a single root cell, and upgraded code panics when that cell does not
+    /// decode into its struct. Existing fields must therefore never be removed,
+    /// reordered or change type, and new state must go into a `Mapping` or `Lazy`
+    /// with its own key, unless the upgrade ships a migration of the root cell.
+    ///
+    /// Upgrading a contract deployed with the original layout, which only held
+    /// `total_supply`, `balances` and `allowances`, to this code is not
+    /// supported: the root cell has grown since.
     #[ink(storage)]
-    #[derive(Default)]
     pub struct Erc20 {
         /// token 发行总量
         total_supply: Balance,
@@ -15,6 +32,246 @@ mod erc20 {
         /// Mapping of the token amount which an account is allowed to withdraw
         /// from another account.
         allowances: Mapping<(AccountId, AccountId), Balance>,
+        /// 合约所有者
+        owner: AccountId,
+        /// Accounts allowed to mint in addition to the owner.
+        minters: Mapping<AccountId, bool>,
+        /// Vesting schedule of each beneficiary, funded from the contract's own account.
+        vesting: Mapping<AccountId, VestingSchedule>,
+        /// token 名称
+        name: Option<String>,
+        /// token 符号
+        symbol: Option<String>,
+        /// Number of decimals used by balances.
+        decimals: u8,
+        /// Decimals reported by `token_decimals` instead of `decimals`, if set.
+        ///
+        /// Only affects presentation; balances always use `decimals`.
+        display_decimals: Option<u8>,
+        /// Dividends distributed per token so far, scaled by `DIVIDEND_MAGNITUDE`.
+        magnified_dividend_per_share: u128,
+        /// Dividends each account has already withdrawn.
+        withdrawn_dividends: Mapping<AccountId, Balance>,
+        /// Magnified dividend adjustments keeping an account's accrued dividends
+        /// unchanged when its balance changes.
+        dividend_corrections: Mapping<AccountId, i128>,
+        /// Domain separator for off-chain signatures, computed at construction.
+        domain_separator: [u8; 32],
+        /// Number of signatures consumed by each owner, used to prevent replays.
+        nonces: Mapping<AccountId, u64>,
+        /// Staking rewards paid per millisecond, shared by all stakers.
+        staking_reward_rate: Balance,
+        /// Tokens currently staked, held by the contract's own account.
+        total_staked: Balance,
+        /// Rewards accrued per staked token so far, scaled by `REWARD_MAGNITUDE`.
+        reward_per_token_stored: u128,
+        /// Block timestamp `reward_per_token_stored` was last brought up to date.
+        reward_updated_at: u64,
+        /// Staking position of each staker.
+        stakes: Mapping<AccountId, StakeInfo>,
+        /// Whether accounts are barred from sending in the block they received in.
+        same_block_guard: bool,
+        /// Block each account last received tokens in, tracked while the guard is on.
+        last_received_block: Mapping<AccountId, BlockNumber>,
+        /// Accounts the same-block guard does not apply to.
+        same_block_exempt: Mapping<AccountId, bool>,
+        /// Decimals of the token's representation on the bridged chain.
+        bridge_decimals: u8,
+        /// Whether batch operations emit only their `BatchTransfer` summary instead
+        /// of one `Transfer` per entry as well.
+        batch_summary_only: bool,
+        /// Timestamp until which each account can't send tokens.
+        locked_until: Mapping<AccountId, u64>,
+        /// Accounts that can't send tokens but can still receive them.
+        frozen: Mapping<AccountId, bool>,
+        /// Part of each account's balance that can't be sent.
+        locked_balance: Mapping<AccountId, Balance>,
+        /// Largest amount a single transfer may move, if capped.
+        max_tx_amount: Option<Balance>,
+        /// Accounts whose transfers, in or out, may exceed `max_tx_amount`.
+        tx_limit_exempt: Mapping<AccountId, bool>,
+        /// Tokens burned over the contract's lifetime.
+        total_burned: Balance,
+        /// Accounts whose balance is not part of the circulating supply.
+        non_circulating: Mapping<AccountId, bool>,
+        /// Sum of the balances of the `non_circulating` accounts.
+        non_circulating_total: Balance,
+        /// Largest shortfall of an allowance that `transfer_from` still accepts.
+        allowance_grace: Balance,
+        /// Tokens held by the contract that each account can claim.
+        pending_withdrawals: Mapping<AccountId, Balance>,
+        /// Whether transfers to contracts outside the allowlist are rejected.
+        reject_contract_recipients: bool,
+        /// Contracts that may receive tokens while contract recipients are rejected.
+        contract_recipient_allowlist: Mapping<AccountId, bool>,
+        /// Ring buffer of the last `HISTORY_LEN` balance changes, by slot.
+        history: Mapping<u32, TransferRecord>,
+        /// Number of balance changes recorded so far; the next one goes into slot
+        /// `history_recorded % HISTORY_LEN`.
+        history_recorded: u64,
+        /// Whether only allowlisted contracts may spend allowances.
+        contract_spenders_only: bool,
+        /// Contracts that may spend allowances while `contract_spenders_only` is set.
+        spender_allowlist: Mapping<AccountId, bool>,
+        /// Whether `decimals` can no longer be corrected.
+        decimals_locked: bool,
+        /// Tokens held by the contract to pay staking rewards from.
+        reward_pool: Balance,
+        /// Timestamp from which each scheduled allowance can be spent.
+        allowance_effective_at: Mapping<(AccountId, AccountId), u64>,
+        /// Top-up budget left to refill each spender's allowance from when it runs
+        /// short.
+        auto_top_up: Mapping<(AccountId, AccountId), Balance>,
+        /// Whether the supply is finalized, disabling minting and burning, except
+        /// by `withdraw`, for good.
+        supply_fixed: bool,
+        /// Native currency held by the contract as backing for wrapped tokens.
+        wrapped_supply: Balance,
+        /// Native currency each account deposited and can still withdraw.
+        wrapped_balances: Mapping<AccountId, Balance>,
+    }
+
+    /// A recorded balance change: `(from, to, value, block)`, with `from` set to
+    /// `None` for mints and `to` set to `None` for burns.
+    pub type TransferRecord = (Option<AccountId>, Option<AccountId>, Balance, BlockNumber);
+
+    /// Number of recent balance changes kept by `recent_transfers`.
+    const HISTORY_LEN: u32 = 32;
+
+    /// Scale of `magnified_dividend_per_share`, keeping precision when a
+    /// distribution is smaller than the number of tokens sharing it.
+    const DIVIDEND_MAGNITUDE: u128 = 1_000_000_000_000;
+
+    /// Maximum length in bytes of a `transfer_with_memo` memo.
+    const MAX_MEMO_LEN: usize = 128;
+
+    /// Scale of `reward_per_token_stored`, keeping precision when few rewards are
+    /// shared by many staked tokens.
+    const REWARD_MAGNITUDE: u128 = 1_000_000_000_000;
+
+    /// Largest difference between `decimals` and `bridge_decimals`, keeping the
+    /// scale factor within `Balance`.
+    const MAX_BRIDGE_DECIMALS_DIFF: u8 = 38;
+
+    /// Version mixed into the signature domain separator.
+    const DOMAIN_VERSION: &str = "1";
+
+    /// Sum of the shares, in basis points, of a `transfer_weighted`.
+    const TOTAL_SHARES: u32 = 10_000;
+
+    /// Maximum number of accounts `freeze_batch` and `unfreeze_batch` accept, keeping
+    /// a call within the gas limit.
+    const MAX_FREEZE_BATCH: usize = 100;
+
+    /// Interface id of `supports_interface` itself.
+    pub const INTERFACE_DETECTION_ID: [u8; 4] = ink::selector_bytes!("supports_interface");
+
+    /// Interface id of the core ERC-20 messages: the XOR of the selectors of
+    /// `total_supply`, `balance_of`, `allowance`, `transfer`, `approve` and
+    /// `transfer_from`.
+    pub const ERC20_INTERFACE_ID: [u8; 4] = xor_selectors(&[
+        ink::selector_bytes!("total_supply"),
+        ink::selector_bytes!("balance_of"),
+        ink::selector_bytes!("allowance"),
+        ink::selector_bytes!("transfer"),
+        ink::selector_bytes!("approve"),
+        ink::selector_bytes!("transfer_from"),
+    ]);
+
+    /// Interface id of the metadata messages: the XOR of the selectors of
+    /// `token_name`, `token_symbol` and `token_decimals`.
+    pub const ERC20_METADATA_INTERFACE_ID: [u8; 4] = xor_selectors(&[
+        ink::selector_bytes!("token_name"),
+        ink::selector_bytes!("token_symbol"),
+        ink::selector_bytes!("token_decimals"),
+    ]);
+
+    /// Returns `value * numerator / denominator`, rounded down.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Overflow` error if `value * numerator` overflows.
+    fn mul_div(value: Balance, numerator: Balance, denominator: Balance) -> Result<Balance> {
+        value
+            .checked_mul(numerator)
+            .map(|product| product / denominator)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Combines message selectors into an interface id, as ERC-165 does.
+    const fn xor_selectors(selectors: &[[u8; 4]]) -> [u8; 4] {
+        let mut id = [0u8; 4];
+        let mut i = 0;
+        while i < selectors.len() {
+            let mut byte = 0;
+            while byte < 4 {
+                id[byte] ^= selectors[i][byte];
+                byte += 1;
+            }
+            i += 1;
+        }
+        id
+    }
+
+    /// A linear vesting schedule.
+    ///
+    /// Nothing is vested before `start`, `total` is vested from `start + duration`
+    /// onwards and the amount grows linearly in between.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct VestingSchedule {
+        /// Total amount locked for the beneficiary.
+        pub total: Balance,
+        /// Amount already released to the beneficiary.
+        pub released: Balance,
+        /// Timestamp at which vesting starts.
+        pub start: u64,
+        /// Time over which `total` vests.
+        pub duration: u64,
+    }
+
+    /// A staker's position.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct StakeInfo {
+        /// Tokens staked.
+        pub staked: Balance,
+        /// `reward_per_token_stored` at the time `rewards` was last updated.
+        pub reward_per_token_paid: u128,
+        /// Rewards accrued but not yet claimed.
+        pub rewards: Balance,
+        /// Whether claimed rewards are restaked instead of paid out.
+        pub auto_compound: bool,
+        /// Whether the owner paused reward accrual for this staker.
+        pub rewards_paused: bool,
+    }
+
+    /// Operational figures shown to admins, returned by `admin_dashboard`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct AdminInfo {
+        /// The contract owner.
+        pub owner: AccountId,
+        /// Total token supply.
+        pub total_supply: Balance,
+        /// Total supply minus the balances of non-circulating accounts.
+        pub circulating_supply: Balance,
+        /// Tokens burned since deployment.
+        pub total_burned: Balance,
+        /// Largest amount a single transfer may move, if capped.
+        pub max_tx_amount: Option<Balance>,
+        /// Whether the same-block guard is enabled.
+        pub same_block_guard: bool,
+        /// Staking rewards paid per millisecond.
+        pub staking_reward_rate: Balance,
+        /// Tokens left to pay staking rewards from.
+        pub reward_pool: Balance,
     }
 
     /// Event emitted when a token transfer occurs.
@@ -27,6 +284,53 @@ mod erc20 {
         value: Balance,
     }
 
+    /// Event emitted alongside `Transfer` when `spender` moves `value` tokens from
+    /// `from` to `to` using its allowance.
+    #[ink(event)]
+    pub struct DelegatedTransfer {
+        #[ink(topic)]
+        spender: AccountId,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted alongside `Transfer` when `sponsor` covered the fees of a
+    /// transfer of `value` tokens from `from` to `to`.
+    #[ink(event)]
+    pub struct SponsoredTransfer {
+        #[ink(topic)]
+        sponsor: AccountId,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted alongside `Transfer` when a transfer carries a `memo`.
+    #[ink(event)]
+    pub struct TransferWithMemo {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+        memo: Vec<u8>,
+    }
+
+    /// Event emitted once per batch operation, summarizing `count` entries moving
+    /// `total` tokens from `from` (`None` for mints).
+    #[ink(event)]
+    pub struct BatchTransfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        count: u32,
+        total: Balance,
+    }
+
     /// Event emitted when an approval occurs that `spender` is allowed to withdraw
     /// up to the amount of `value` tokens from `owner`.
     #[ink(event)]
@@ -38,14 +342,200 @@ mod erc20 {
         value: Balance,
     }
 
+    /// Event emitted alongside an `Approval` of zero when `owner` revokes the
+    /// allowance of `spender` entirely.
+    #[ink(event)]
+    pub struct ApprovalRevoked {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+    }
+
+    /// Event emitted when `account` is granted minter rights.
+    #[ink(event)]
+    pub struct MinterGranted {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when `account` has its minter rights revoked.
+    #[ink(event)]
+    pub struct MinterRevoked {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when `account` is frozen.
+    #[ink(event)]
+    pub struct AccountFrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when `account` is unfrozen.
+    #[ink(event)]
+    pub struct AccountUnfrozen {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Event emitted when `value` tokens are distributed as dividends to all holders.
+    #[ink(event)]
+    pub struct DividendsDistributed {
+        #[ink(topic)]
+        from: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `to` withdraws `value` tokens of dividends.
+    #[ink(event)]
+    pub struct DividendWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `staker` stakes `value` tokens.
+    #[ink(event)]
+    pub struct Staked {
+        #[ink(topic)]
+        staker: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `staker` unstakes `value` tokens.
+    #[ink(event)]
+    pub struct Unstaked {
+        #[ink(topic)]
+        staker: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `staker` claims `value` tokens of staking rewards.
+    #[ink(event)]
+    pub struct StakingRewardClaimed {
+        #[ink(topic)]
+        staker: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `to` deposits `value` native currency, minting as many tokens.
+    #[ink(event)]
+    pub struct Deposit {
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when `from` burns `value` tokens to withdraw as much native
+    /// currency.
+    #[ink(event)]
+    pub struct Withdrawal {
+        #[ink(topic)]
+        from: AccountId,
+        value: Balance,
+    }
+
+    /// Event emitted when the supply is finalized at `total_supply`.
+    #[ink(event)]
+    pub struct SupplyFinalized {
+        total_supply: Balance,
+    }
+
+    /// Event emitted when the contract code is upgraded to `code_hash`.
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
     /// The ERC-20 error types.
+    ///
+    /// When a transfer breaks several rules at once, the error of the first broken
+    /// rule is returned, in this order:
+    ///
+    /// 1. `AccountFrozen`
+    /// 2. `SameBlockTransfer`
+    /// 3. `TokensLocked`
+    /// 4. `ContractRecipientRejected`
+    /// 5. `ExceedsMaxTx`
+    /// 6. `InsufficientBalance`
+    /// 7. `InsufficientUnlockedBalance`
+    ///
+    /// Rules on the sender come before rules on the recipient, then limits on the
+    /// amount, and balance checks come last. Messages check the caller's
+    /// permissions (`NotOwner`, `InsufficientAllowance`, ...) before any of these.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         /// Returned if not enough balance to fulfill a request is available.
-        InsufficientBalance,
+        InsufficientBalance { available: Balance, requested: Balance },
         /// Returned if not enough allowance to fulfill a request is available.
-        InsufficientAllowance,
+        InsufficientAllowance { available: Balance, requested: Balance },
+        /// Returned if the caller is not the contract owner.
+        NotOwner,
+        /// Returned if the caller is not allowed to mint.
+        NotMinter,
+        /// Returned if an arithmetic operation would overflow.
+        Overflow,
+        /// Returned if the beneficiary already has a vesting schedule.
+        VestingExists,
+        /// Returned if there are no vested tokens left to release.
+        NothingToRelease,
+        /// Returned if a dividend distribution has nothing to distribute or no
+        /// holders to distribute to.
+        NothingToDistribute,
+        /// Returned if the caller has no dividends to withdraw.
+        NoDividend,
+        /// Returned if the current allowance differs from the expected one.
+        AllowanceMismatch,
+        /// Returned if the runtime rejected the new code hash.
+        SetCodeHashFailed,
+        /// Returned if a memo is longer than `MAX_MEMO_LEN` bytes.
+        MemoTooLong,
+        /// Returned if the caller has staked less than the amount to unstake.
+        InsufficientStake,
+        /// Returned if the caller has no staking rewards to claim.
+        NoReward,
+        /// Returned if the sender received tokens in the current block.
+        SameBlockTransfer,
+        /// Returned if the contract failed to send native currency.
+        NativeTransferFailed,
+        /// Returned if bridge decimals differ too much from the token decimals.
+        InvalidBridgeDecimals,
+        /// Returned if the sender's tokens are locked until a later timestamp.
+        TokensLocked,
+        /// Returned if the sender is frozen.
+        AccountFrozen,
+        /// Returned if the sender's unlocked balance is less than the amount sent.
+        InsufficientUnlockedBalance,
+        /// Returned if a transfer exceeds the maximum transaction amount.
+        ExceedsMaxTx,
+        /// Returned if the recipient is a contract that is not allowlisted while
+        /// contract recipients are rejected.
+        ContractRecipientRejected,
+        /// Returned if the spender is not an allowlisted contract while only those
+        /// may spend allowances.
+        SpenderNotContract,
+        /// Returned if the decimals are locked and can no longer be changed.
+        DecimalsLocked,
+        /// Returned if the reward pool can't cover the rewards claimed.
+        RewardPoolEmpty,
+        /// Returned if shares don't add up to `TOTAL_SHARES`.
+        InvalidShares,
+        /// Returned if a batch has more than `MAX_FREEZE_BATCH` entries.
+        BatchTooLarge,
+        /// Returned if tokens are minted, burned or wrapped after the supply was
+        /// finalized.
+        SupplyFixed,
+        /// Returned if a withdrawal exceeds the native currency the caller deposited.
+        InsufficientBacking,
+        /// Returned if a sponsored transfer carries no sponsor fee.
+        ZeroAmount,
+        /// Returned if the contract's own account, which holds vesting, dividend,
+        /// staking and pending tokens in custody, would be frozen or locked.
+        CustodyAccount,
     }
 
     /// The ERC-20 result type.
@@ -55,222 +545,4336 @@ mod erc20 {
         // 合约初始化
         #[ink(constructor)]
         pub fn new(total_supply: Balance) -> Self {
-            // 初始化 Mapping 实例
-            let mut balances = Mapping::default();
+            Self::new_with_metadata(total_supply, None, None, 0)
+        }
+
+        /// Creates a token with the given metadata, assigning `total_supply` to the caller.
+        #[ink(constructor)]
+        pub fn new_with_metadata(
+            total_supply: Balance,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
             // 当前调用者
             let caller = Self::env().caller();
-            balances.insert(caller, &total_supply);
             // total_supply 总量给于 当前调用者
-            Self::env().emit_event(Transfer {
-                from: None,
-                to: Some(caller),
-                value: total_supply,
-            });
+            Self::init(vec![(caller, total_supply)], name, symbol, decimals)
+        }
+
+        /// Creates a token whose total supply is the sum of `allocations`, assigning
+        /// each account its allocation.
+        ///
+        /// Duplicate accounts are merged into one allocation of their sum.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the total supply would overflow.
+        ///
+        /// 初始分配代币
+        #[ink(constructor)]
+        pub fn new_with_allocations(allocations: Vec<(AccountId, Balance)>) -> Self {
+            Self::init(allocations, None, None, 0)
+        }
+
+        /// Builds the contract state, minting each of `allocations` and emitting
+        /// one `Transfer` event and history record per distinct account.
+        fn init(
+            allocations: Vec<(AccountId, Balance)>,
+            name: Option<String>,
+            symbol: Option<String>,
+            decimals: u8,
+        ) -> Self {
+            let mut merged: Vec<(AccountId, Balance)> = Vec::new();
+            for (account, value) in allocations {
+                match merged.iter_mut().find(|(merged_account, _)| *merged_account == account) {
+                    Some((_, merged_value)) => {
+                        *merged_value = merged_value.checked_add(value).expect("total supply overflow")
+                    }
+                    None => merged.push((account, value)),
+                }
+            }
+            let total_supply = merged
+                .iter()
+                .try_fold(0, |total: Balance, (_, value)| total.checked_add(*value))
+                .expect("total supply overflow");
+            // 初始化 Mapping 实例
+            let mut balances = Mapping::default();
+            for &(account, value) in &merged {
+                balances.insert(account, &value);
+                Self::env().emit_event(Transfer {
+                    from: None,
+                    to: Some(account),
+                    value,
+                });
+            }
+            let caller = Self::env().caller();
+            let domain_separator = Self::compute_domain_separator(&name);
             // 反回合约初始化结构对象
-            Self {
+            let mut contract = Self {
                 total_supply,
                 balances,
                 allowances: Default::default(),
+                owner: caller,
+                minters: Default::default(),
+                vesting: Default::default(),
+                name,
+                symbol,
+                decimals,
+                display_decimals: None,
+                magnified_dividend_per_share: 0,
+                withdrawn_dividends: Default::default(),
+                dividend_corrections: Default::default(),
+                domain_separator,
+                nonces: Default::default(),
+                staking_reward_rate: 0,
+                total_staked: 0,
+                reward_per_token_stored: 0,
+                reward_updated_at: Self::env().block_timestamp(),
+                stakes: Default::default(),
+                same_block_guard: false,
+                last_received_block: Default::default(),
+                same_block_exempt: Default::default(),
+                bridge_decimals: decimals,
+                batch_summary_only: false,
+                locked_until: Default::default(),
+                frozen: Default::default(),
+                locked_balance: Default::default(),
+                max_tx_amount: None,
+                tx_limit_exempt: Default::default(),
+                total_burned: 0,
+                non_circulating: Default::default(),
+                non_circulating_total: 0,
+                allowance_grace: 0,
+                pending_withdrawals: Default::default(),
+                reject_contract_recipients: false,
+                contract_recipient_allowlist: Default::default(),
+                history: Default::default(),
+                history_recorded: 0,
+                contract_spenders_only: false,
+                spender_allowlist: Default::default(),
+                decimals_locked: false,
+                reward_pool: 0,
+                allowance_effective_at: Default::default(),
+                auto_top_up: Default::default(),
+                supply_fixed: false,
+                wrapped_supply: 0,
+                wrapped_balances: Default::default(),
+            };
+            for (account, value) in merged {
+                contract.record_transfer(None, Some(account), value);
             }
+            contract
         }
 
-        /// Returns the total token supply.
+        /// Returns the contract owner.
         #[ink(message)]
-        pub fn total_supply(&self) -> Balance {
-            self.total_supply
+        pub fn owner(&self) -> AccountId {
+            self.owner
         }
 
-        /// Returns the account balance for the specified `owner`.
+        /// Returns `true` if `account` is allowed to mint.
         ///
-        /// 返回用户余额
+        /// The owner is always a minter.
         #[ink(message)]
-        pub fn balance_of(&self, owner: AccountId) -> Balance {
-            self.balance_of_impl(&owner)
+        pub fn is_minter(&self, account: AccountId) -> bool {
+            account == self.owner || self.minters.get(account).unwrap_or_default()
         }
 
-        /// Returns the account balance for the specified `owner`.
+        /// Grants minter rights to `account`.
         ///
-        /// Returns `0` if the account is non-existent.
+        /// On success a `MinterGranted` event is emitted.
         ///
-        /// # Note
-        /// 内部查询余额方法
-        #[inline]
-        fn balance_of_impl(&self, owner: &AccountId) -> Balance {
-            self.balances.get(owner).unwrap_or_default()
-        }
-
-        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        /// # Errors
         ///
-        /// Returns `0` if no allowance has been set.
+        /// Returns `NotOwner` error if the caller is not the owner.
         #[ink(message)]
-        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
-            self.allowance_impl(&owner, &spender)
+        pub fn grant_minter(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.minters.insert(account, &true);
+            self.env().emit_event(MinterGranted { account });
+            Ok(())
         }
 
-        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        /// Revokes minter rights from `account`.
         ///
-        /// Returns `0` if no allowance has been set.
+        /// The owner stays a minter regardless.
         ///
-        /// # Note
+        /// On success a `MinterRevoked` event is emitted.
         ///
-        /// Prefer to call this method over `allowance` since this
-        /// works using references which are more efficient in Wasm.
-        #[inline]
-        fn allowance_impl(&self, owner: &AccountId, spender: &AccountId) -> Balance {
-            self.allowances.get((owner, spender)).unwrap_or_default()
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn revoke_minter(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.minters.remove(account);
+            self.env().emit_event(MinterRevoked { account });
+            Ok(())
         }
 
-        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        /// Creates `value` new tokens and assigns them to `to`.
         ///
-        /// On success a `Transfer` event is emitted.
+        /// On success a `Transfer` event with `from: None` is emitted.
         ///
         /// # Errors
         ///
-        /// 代币转账 to other
+        /// Returns `NotMinter` error if the caller is not a minter.
+        ///
+        /// Returns `SupplyFixed` error if the supply is finalized.
+        ///
+        /// Returns `Overflow` error if the total supply would overflow or grow past
+        /// what the dividend accounting can represent.
+        ///
+        /// 铸造代币
         #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
-            let from = self.env().caller();
-            self.transfer_from_to(&from, &to, value)
+        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::NotMinter)
+            }
+            self.mint_impl(&to, value)
         }
 
-        /// Allows `spender` to withdraw from the caller's account multiple times, up to
-        /// the `value` amount.
+        /// Fixes the supply for good: minting, burning and wrapping the native
+        /// currency are disabled, so the supply can no longer grow.
         ///
-        /// If this function is called again it overwrites the current allowance with
-        /// `value`.
+        /// Unwrapping may still reduce the supply: wrapped tokens can be redeemed
+        /// with `withdraw`, which burns them, so deposits are never stuck in the
+        /// contract.
         ///
-        /// 授予转账
+        /// On success a `SupplyFinalized` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `SupplyFixed` error if the supply is already finalized.
+        ///
+        /// 固定总量
         #[ink(message)]
-        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
-            let owner = self.env().caller();
-            self.allowances.insert((&owner, &spender), &value);
-            self.env().emit_event(Approval {
-                owner,
-                spender,
-                value,
+        pub fn finalize_supply(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            if self.supply_fixed {
+                return Err(Error::SupplyFixed)
+            }
+            self.supply_fixed = true;
+            self.env().emit_event(SupplyFinalized {
+                total_supply: self.total_supply,
             });
             Ok(())
         }
 
-        /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+        /// Returns `true` if the supply is finalized.
+        #[ink(message)]
+        pub fn supply_fixed(&self) -> bool {
+            self.supply_fixed
+        }
+
+        /// Mints to each `(recipient, value)` of `entries`.
         ///
-        /// This can be used to allow a contract to transfer tokens on ones behalf and/or
-        /// to charge fees in sub-currencies, for example.
+        /// The whole batch is checked before anything is minted, so either every
+        /// entry is minted or none is.
         ///
-        /// On success a `Transfer` event is emitted.
+        /// On success a `BatchTransfer` event with `from: None` is emitted, preceded
+        /// by a `Transfer` event with `from: None` per entry unless
+        /// `batch_summary_only` is set.
         ///
         /// # Errors
         ///
-        /// Returns `InsufficientAllowance` error if there are not enough tokens allowed
-        /// for the caller to withdraw from `from`.
+        /// Returns `NotMinter` error if the caller is not a minter.
         ///
-        /// 授予转账，提币出来
+        /// Returns `SupplyFixed` error if the supply is finalized.
+        ///
+        /// Returns `Overflow` error if the total supply would overflow or grow past
+        /// what the dividend accounting can represent.
+        ///
+        /// 批量铸造代币
         #[ink(message)]
-        pub fn transfer_from(
-            &mut self,
-            from: AccountId,
-            to: AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let caller = self.env().caller();
-            // 检查是否授予转账
-            let allowance = self.allowance_impl(&from, &caller);
-            if allowance < value {
-                return Err(Error::InsufficientAllowance)
+        pub fn mint_batch(&mut self, entries: Vec<(AccountId, Balance)>) -> Result<()> {
+            if !self.is_minter(self.env().caller()) {
+                return Err(Error::NotMinter)
             }
-            // 转账代币
-            self.transfer_from_to(&from, &to, value)?;
-            self.allowances
-                .insert((&from, &caller), &(allowance - value));
+            let total = entries
+                .iter()
+                .try_fold(0, |total: Balance, (_, value)| total.checked_add(*value))
+                .filter(|total| {
+                    matches!(
+                        self.total_supply.checked_add(*total),
+                        Some(supply) if Self::dividends_fit(self.magnified_dividend_per_share, supply)
+                    )
+                })
+                .ok_or(Error::Overflow)?;
+            let count = entries.len() as u32;
+            for (to, value) in entries {
+                if self.batch_summary_only {
+                    self.mint_balance(&to, value)?;
+                } else {
+                    self.mint_impl(&to, value)?;
+                }
+            }
+            self.env().emit_event(BatchTransfer {
+                from: None,
+                count,
+                total,
+            });
             Ok(())
         }
 
-        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        /// Wraps the native currency sent with the call, minting the caller as many
+        /// tokens. Only the caller can redeem them for the native currency again.
         ///
-        /// On success a `Transfer` event is emitted.
+        /// On success a `Transfer` event with `from: None` and a `Deposit` event are
+        /// emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `SupplyFixed` error if the supply is finalized.
+        ///
+        /// Returns `Overflow` error if the total supply would overflow or grow past
+        /// what the dividend accounting can represent.
+        ///
+        /// 存入原生代币
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) -> Result<()> {
+            let to = self.env().caller();
+            let value = self.env().transferred_value();
+            self.mint_impl(&to, value)?;
+            // the backing can never exceed the total supply
+            self.wrapped_supply += value;
+            let wrapped = self.wrapped_balance_of(to) + value;
+            self.wrapped_balances.insert(to, &wrapped);
+            self.env().emit_event(Deposit { to, value });
+            Ok(())
+        }
+
+        /// Burns `value` of the caller's tokens and sends the caller as much native
+        /// currency, up to what the caller deposited and hasn't withdrawn yet.
+        ///
+        /// Tokens not minted by `deposit`, including deposited tokens received from
+        /// other accounts, aren't backed for the caller and can't be redeemed.
+        ///
+        /// Works even after `finalize_supply`, reducing the fixed supply.
+        ///
+        /// On success a `Transfer` event with `to: None` and a `Withdrawal` event are
+        /// emitted.
         ///
         /// # Errors
         ///
+        /// Returns `InsufficientBacking` error if the caller deposited less than
+        /// `value` that it hasn't withdrawn yet.
+        ///
         /// Returns `InsufficientBalance` error if there are not enough tokens on
         /// the caller's account balance.
-        fn transfer_from_to(
-            &mut self,
-            from: &AccountId,
-            to: &AccountId,
-            value: Balance,
-        ) -> Result<()> {
-            let from_balance = self.balance_of_impl(from);
-            if from_balance < value {
-                return Err(Error::InsufficientBalance)
+        ///
+        /// Returns `NativeTransferFailed` error if the native currency could not be
+        /// sent, in which case the call is reverted and no tokens are burned.
+        ///
+        /// 取回原生代币
+        #[ink(message)]
+        pub fn withdraw(&mut self, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            let wrapped = self.wrapped_balance_of(from);
+            if wrapped < value {
+                return Err(Error::InsufficientBacking)
             }
+            self.burn_impl(&from, value)?;
+            self.wrapped_supply -= value;
+            self.wrapped_balances.insert(from, &(wrapped - value));
+            self.env()
+                .transfer(from, value)
+                .map_err(|_| Error::NativeTransferFailed)?;
+            self.env().emit_event(Withdrawal { from, value });
+            Ok(())
+        }
 
-            self.balances.insert(from, &(from_balance - value));
-            let to_balance = self.balance_of_impl(to);
-            self.balances.insert(to, &(to_balance + value));
-            self.env().emit_event(Transfer {
-                from: Some(*from),
-                to: Some(*to),
-                value,
+        /// Returns the native currency held as backing for wrapped tokens.
+        #[ink(message)]
+        pub fn wrapped_supply(&self) -> Balance {
+            self.wrapped_supply
+        }
+
+        /// Returns the native currency `account` deposited and can still withdraw.
+        #[ink(message)]
+        pub fn wrapped_balance_of(&self, account: AccountId) -> Balance {
+            self.wrapped_balances.get(account).unwrap_or_default()
+        }
+
+        /// Sets whether batch operations emit only their `BatchTransfer` summary.
+        ///
+        /// Summary-only batches keep the event log small, but indexers then can't
+        /// see individual recipients and amounts from events alone and have to
+        /// decode the call instead.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_batch_summary_only(&mut self, summary_only: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.batch_summary_only = summary_only;
+            Ok(())
+        }
+
+        /// Burns `value` tokens from the caller's account and reduces the caller's
+        /// allowance to `spender` by the same amount, floored at zero.
+        ///
+        /// On success a `Transfer` event with `to: None` and an `Approval` event
+        /// with the reduced allowance are emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `SupplyFixed` error if the supply is finalized.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        ///
+        /// 销毁代币并同步减少授权额度
+        #[ink(message)]
+        pub fn burn_and_revoke(&mut self, value: Balance, spender: AccountId) -> Result<()> {
+            if self.supply_fixed {
+                return Err(Error::SupplyFixed)
+            }
+            let owner = self.env().caller();
+            self.burn_impl(&owner, value)?;
+            let allowance = self
+                .allowance_impl(&owner, &spender)
+                .saturating_sub(value);
+            self.allowances.insert((&owner, &spender), &allowance);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: allowance,
             });
             Ok(())
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        /// Locks `total` tokens of the owner for `beneficiary`, vesting linearly over
+        /// `duration` starting at `start`.
+        ///
+        /// The tokens are moved into the contract's own account until released.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `VestingExists` error if `beneficiary` already has a schedule.
+        ///
+        /// Returns `InsufficientBalance` error if the owner holds less than `total`.
+        #[ink(message)]
+        pub fn create_vesting(
+            &mut self,
+            beneficiary: AccountId,
+            total: Balance,
+            start: u64,
+            duration: u64,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            if self.vesting.contains(beneficiary) {
+                return Err(Error::VestingExists)
+            }
+            let owner = self.owner;
+            let contract = self.env().account_id();
+            self.transfer_from_to(&owner, &contract, total)?;
+            self.vesting.insert(
+                beneficiary,
+                &VestingSchedule {
+                    total,
+                    released: 0,
+                    start,
+                    duration,
+                },
+            );
+            Ok(())
+        }
+
+        /// Releases the caller's vested but not yet released tokens.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NothingToRelease` error if no tokens are currently releasable.
+        #[ink(message)]
+        pub fn release(&mut self) -> Result<()> {
+            let beneficiary = self.env().caller();
+            let mut schedule = self
+                .vesting
+                .get(beneficiary)
+                .ok_or(Error::NothingToRelease)?;
+            let releasable = self.vested_amount_impl(&schedule) - schedule.released;
+            if releasable == 0 {
+                return Err(Error::NothingToRelease)
+            }
+            let contract = self.env().account_id();
+            self.transfer_from_to(&contract, &beneficiary, releasable)?;
+            schedule.released += releasable;
+            self.vesting.insert(beneficiary, &schedule);
+            Ok(())
+        }
+
+        /// Returns the amount vested so far for `account`, including released tokens.
+        ///
+        /// Returns `0` if `account` has no vesting schedule.
+        #[ink(message)]
+        pub fn vested_amount(&self, account: AccountId) -> Balance {
+            self.vesting
+                .get(account)
+                .map(|schedule| self.vested_amount_impl(&schedule))
+                .unwrap_or_default()
+        }
+
+        /// Returns the amount `account` could release right now.
+        ///
+        /// Returns `0` if `account` has no vesting schedule.
+        #[ink(message)]
+        pub fn releasable(&self, account: AccountId) -> Balance {
+            self.vesting
+                .get(account)
+                .map(|schedule| self.vested_amount_impl(&schedule) - schedule.released)
+                .unwrap_or_default()
+        }
+
+        /// Returns the token name.
+        #[ink(message)]
+        pub fn token_name(&self) -> Option<String> {
+            self.name.clone()
+        }
+
+        /// Returns the token symbol.
+        #[ink(message)]
+        pub fn token_symbol(&self) -> Option<String> {
+            self.symbol.clone()
+        }
+
+        /// Returns the token decimals for display.
+        ///
+        /// This is the display override if one is set, otherwise the decimals
+        /// balances are denominated in.
+        #[ink(message)]
+        pub fn token_decimals(&self) -> u8 {
+            self.display_decimals.unwrap_or(self.decimals)
+        }
+
+        /// Returns the token name, symbol, display decimals and total supply in one call.
+        #[ink(message)]
+        pub fn metadata(&self) -> (Option<String>, Option<String>, u8, Balance) {
+            (
+                self.token_name(),
+                self.token_symbol(),
+                self.token_decimals(),
+                self.total_supply,
+            )
+        }
+
+        /// Overrides the decimals reported by `token_decimals`, or clears the
+        /// override with `None`.
+        ///
+        /// Balances and transfers keep using the real decimals.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_display_decimals(&mut self, display_decimals: Option<u8>) -> Result<()> {
+            self.ensure_owner()?;
+            self.display_decimals = display_decimals;
+            Ok(())
+        }
+
+        /// Corrects the decimals balances are denominated in, for deployments made
+        /// with the wrong ones. Only possible until `lock_decimals` is called.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `DecimalsLocked` error if the decimals are locked.
+        ///
+        /// Returns `InvalidBridgeDecimals` error if `decimals` differs from the bridge
+        /// decimals by more than `MAX_BRIDGE_DECIMALS_DIFF`.
+        #[ink(message)]
+        pub fn set_decimals(&mut self, decimals: u8) -> Result<()> {
+            self.ensure_owner()?;
+            if self.decimals_locked {
+                return Err(Error::DecimalsLocked)
+            }
+            if decimals.abs_diff(self.bridge_decimals) > MAX_BRIDGE_DECIMALS_DIFF {
+                return Err(Error::InvalidBridgeDecimals)
+            }
+            self.decimals = decimals;
+            Ok(())
+        }
+
+        /// Locks the decimals permanently.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn lock_decimals(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+            self.decimals_locked = true;
+            Ok(())
+        }
+
+        /// Returns `true` if the decimals are locked.
+        #[ink(message)]
+        pub fn decimals_locked(&self) -> bool {
+            self.decimals_locked
+        }
+
+        /// Distributes `value` of the caller's tokens as dividends to all holders in
+        /// proportion to their balances.
+        ///
+        /// The tokens are moved into the contract's own account until withdrawn.
+        /// Tokens held by the contract itself do not earn dividends.
+        ///
+        /// On success a `DividendsDistributed` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NothingToDistribute` error if `value` is zero, no tokens are
+        /// held outside the contract or `value` is too small to credit any holder.
+        ///
+        /// Returns `InsufficientBalance` error if the caller holds less than `value`.
+        ///
+        /// Returns `Overflow` error if the per-share accumulator would overflow, or
+        /// if the dividends it implies on the total supply would no longer fit in
+        /// an `i128`.
+        ///
+        /// 按持币比例分红
+        #[ink(message)]
+        pub fn distribute_dividends(&mut self, value: Balance) -> Result<()> {
+            if value == 0 {
+                return Err(Error::NothingToDistribute)
+            }
+            let from = self.env().caller();
+            let contract = self.env().account_id();
+            let circulating = self.total_supply - self.balance_of_impl(&contract);
+            // `from`'s contribution stops earning once it moves to the contract
+            let shares = circulating.saturating_sub(value);
+            if shares == 0 {
+                return Err(Error::NothingToDistribute)
+            }
+            let increase = value
+                .checked_mul(DIVIDEND_MAGNITUDE)
+                .ok_or(Error::Overflow)?
+                / shares;
+            // a zero increase would strand `value` in the contract
+            if increase == 0 {
+                return Err(Error::NothingToDistribute)
+            }
+            let per_share = self
+                .magnified_dividend_per_share
+                .checked_add(increase)
+                .filter(|per_share| Self::dividends_fit(*per_share, self.total_supply))
+                .ok_or(Error::Overflow)?;
+            self.transfer_from_to(&from, &contract, value)?;
+            self.magnified_dividend_per_share = per_share;
+            self.env().emit_event(DividendsDistributed { from, value });
+            Ok(())
+        }
+
+        /// Withdraws all dividends owed to the caller.
+        ///
+        /// On success a `DividendWithdrawn` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NoDividend` error if the caller has no dividends to withdraw.
+        #[ink(message)]
+        pub fn withdraw_dividend(&mut self) -> Result<()> {
+            let to = self.env().caller();
+            let value = self.withdrawable_dividend_of(to);
+            if value == 0 {
+                return Err(Error::NoDividend)
+            }
+            let contract = self.env().account_id();
+            self.transfer_from_to(&contract, &to, value)?;
+            let withdrawn = self.withdrawn_dividends.get(to).unwrap_or_default();
+            self.withdrawn_dividends.insert(to, &(withdrawn + value));
+            self.env().emit_event(DividendWithdrawn { to, value });
+            Ok(())
+        }
+
+        /// Returns the total dividends `account` has earned, including withdrawn ones.
+        #[ink(message)]
+        pub fn accumulated_dividend_of(&self, account: AccountId) -> Balance {
+            let magnified = Self::magnified_dividends(
+                self.magnified_dividend_per_share,
+                self.balance_of_impl(&account),
+            );
+            let corrected = magnified
+                .checked_add(self.dividend_corrections.get(account).unwrap_or_default())
+                .and_then(|corrected| u128::try_from(corrected).ok())
+                .expect("dividend corrections out of range");
+            corrected / DIVIDEND_MAGNITUDE
+        }
+
+        /// Returns the dividends `account` can currently withdraw.
+        #[ink(message)]
+        pub fn withdrawable_dividend_of(&self, account: AccountId) -> Balance {
+            self.accumulated_dividend_of(account)
+                .saturating_sub(self.withdrawn_dividends.get(account).unwrap_or_default())
+        }
+
+        /// Moves all tokens pending for the caller into the caller's balance and
+        /// returns the amount claimed, which is `0` if nothing is pending.
+        ///
+        /// On success a `Transfer` event is emitted if anything was claimed.
+        #[ink(message)]
+        pub fn claim_pending(&mut self) -> Result<Balance> {
+            let to = self.env().caller();
+            let value = self.pending_of(to);
+            if value == 0 {
+                return Ok(0)
+            }
+            let contract = self.env().account_id();
+            self.transfer_from_to(&contract, &to, value)?;
+            self.pending_withdrawals.remove(to);
+            Ok(value)
+        }
+
+        /// Returns the tokens `account` can claim with `claim_pending`.
+        #[ink(message)]
+        pub fn pending_of(&self, account: AccountId) -> Balance {
+            self.pending_withdrawals.get(account).unwrap_or_default()
+        }
+
+        /// Returns the domain separator signed messages for this token must commit to.
+        ///
+        /// It is the Keccak-256 hash of the SCALE-encoded token name, `DOMAIN_VERSION`
+        /// and this contract's account id.
+        ///
+        /// # Note
+        ///
+        /// The contracts environment exposes no chain id, so unlike EIP-712 it is not
+        /// part of the hash; the contract account id scopes signatures instead.
+        #[ink(message)]
+        pub fn domain_separator(&self) -> [u8; 32] {
+            self.domain_separator
+        }
+
+        /// Returns the next signature nonce of `owner`.
+        #[ink(message)]
+        pub fn nonces(&self, owner: AccountId) -> u64 {
+            self.nonces.get(owner).unwrap_or_default()
+        }
+
+        /// Replaces the contract code with the code uploaded under `code_hash`,
+        /// keeping the storage.
+        ///
+        /// The new code must decode the existing root cell; see the storage layout
+        /// notes on `Erc20`.
+        ///
+        /// On success a `CodeUpgraded` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `SetCodeHashFailed` error if the runtime rejects `code_hash`, for
+        /// example because no code was uploaded under it.
+        ///
+        /// 升级合约代码
+        #[ink(message)]
+        pub fn set_code_hash(&mut self, code_hash: Hash) -> Result<()> {
+            self.ensure_owner()?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::SetCodeHashFailed)?;
+            self.env().emit_event(CodeUpgraded { code_hash });
+            Ok(())
+        }
+
+        /// Stakes `value` of the caller's tokens, accruing staking rewards until
+        /// they are unstaked.
+        ///
+        /// The tokens are moved into the contract's own account.
+        ///
+        /// On success a `Staked` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if the caller holds less than `value`.
+        ///
+        /// 质押代币
+        #[ink(message)]
+        pub fn stake(&mut self, value: Balance) -> Result<()> {
+            let staker = self.env().caller();
+            let mut stake = self.update_reward(&staker);
+            let contract = self.env().account_id();
+            self.transfer_from_to(&staker, &contract, value)?;
+            stake.staked += value;
+            self.total_staked += value;
+            self.stakes.insert(staker, &stake);
+            self.env().emit_event(Staked { staker, value });
+            Ok(())
+        }
+
+        /// Unstakes `value` of the caller's staked tokens.
+        ///
+        /// Accrued rewards stay claimable.
+        ///
+        /// On success an `Unstaked` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientStake` error if the caller staked less than `value`.
+        ///
+        /// 解除质押
+        #[ink(message)]
+        pub fn unstake(&mut self, value: Balance) -> Result<()> {
+            let staker = self.env().caller();
+            let mut stake = self.update_reward(&staker);
+            if stake.staked < value {
+                return Err(Error::InsufficientStake)
+            }
+            let contract = self.env().account_id();
+            self.transfer_from_to(&contract, &staker, value)?;
+            stake.staked -= value;
+            self.total_staked -= value;
+            self.stakes.insert(staker, &stake);
+            self.env().emit_event(Unstaked { staker, value });
+            Ok(())
+        }
+
+        /// Pays all staking rewards accrued by the caller from the reward pool to the
+        /// caller, or adds them to the caller's stake if auto-compounding is enabled.
+        ///
+        /// On success a `StakingRewardClaimed` event is emitted, followed by a
+        /// `Staked` event when the rewards are restaked.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NoReward` error if the caller has no rewards to claim.
+        ///
+        /// Returns `RewardPoolEmpty` error if the reward pool holds less than the
+        /// rewards, which stay claimable once the pool is funded.
+        ///
+        /// 领取质押奖励
+        #[ink(message)]
+        pub fn claim_staking_rewards(&mut self) -> Result<()> {
+            let staker = self.env().caller();
+            let mut stake = self.update_reward(&staker);
+            let value = stake.rewards;
+            if value == 0 {
+                return Err(Error::NoReward)
+            }
+            if self.reward_pool < value {
+                return Err(Error::RewardPoolEmpty)
+            }
+            if stake.auto_compound {
+                stake.staked += value;
+                self.total_staked += value;
+            } else {
+                let contract = self.env().account_id();
+                self.transfer_from_to(&contract, &staker, value)?;
+            }
+            self.reward_pool -= value;
+            stake.rewards = 0;
+            self.stakes.insert(staker, &stake);
+            self.env().emit_event(StakingRewardClaimed { staker, value });
+            if stake.auto_compound {
+                self.env().emit_event(Staked { staker, value });
+            }
+            Ok(())
+        }
+
+        /// Adds `value` of the caller's tokens to the reward pool staking rewards are
+        /// paid from.
+        ///
+        /// The tokens are moved into the contract's own account.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if the caller holds less than `value`.
+        ///
+        /// 注入质押奖励池
+        #[ink(message)]
+        pub fn fund_rewards(&mut self, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            let contract = self.env().account_id();
+            self.transfer_from_to(&from, &contract, value)?;
+            // the pool never exceeds the contract's balance
+            self.reward_pool += value;
+            Ok(())
+        }
+
+        /// Returns the tokens left to pay staking rewards from.
+        #[ink(message)]
+        pub fn reward_pool(&self) -> Balance {
+            self.reward_pool
+        }
+
+        /// Enables or disables restaking the caller's claimed rewards.
+        #[ink(message)]
+        pub fn set_auto_compound(&mut self, enabled: bool) -> Result<()> {
+            let staker = self.env().caller();
+            let mut stake = self.stakes.get(staker).unwrap_or_default();
+            stake.auto_compound = enabled;
+            self.stakes.insert(staker, &stake);
+            Ok(())
+        }
+
+        /// Stops reward accrual for `account` while keeping its stake and the rewards
+        /// accrued so far.
+        ///
+        /// The rewards its stake would have earned meanwhile are not redistributed.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn pause_rewards(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            let mut stake = self.update_reward(&account);
+            stake.rewards_paused = true;
+            self.stakes.insert(account, &stake);
+            Ok(())
+        }
+
+        /// Restarts reward accrual for `account` from now on.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn resume_rewards(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            let mut stake = self.update_reward(&account);
+            stake.rewards_paused = false;
+            self.stakes.insert(account, &stake);
+            Ok(())
+        }
+
+        /// Sets the staking rewards paid per millisecond, shared by all stakers.
+        ///
+        /// Rewards accrued so far keep the previous rate.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_staking_reward_rate(&mut self, rate: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.reward_per_token_stored = self.reward_per_token();
+            self.reward_updated_at = self.env().block_timestamp();
+            self.staking_reward_rate = rate;
+            Ok(())
+        }
+
+        /// Returns the staking rewards paid per millisecond.
+        #[ink(message)]
+        pub fn staking_reward_rate(&self) -> Balance {
+            self.staking_reward_rate
+        }
+
+        /// Returns the total amount of staked tokens.
+        #[ink(message)]
+        pub fn total_staked(&self) -> Balance {
+            self.total_staked
+        }
+
+        /// Returns the amount of tokens `account` has staked.
+        #[ink(message)]
+        pub fn staked_of(&self, account: AccountId) -> Balance {
+            self.stakes.get(account).unwrap_or_default().staked
+        }
+
+        /// Returns the staking rewards `account` could claim right now.
+        #[ink(message)]
+        pub fn earned(&self, account: AccountId) -> Balance {
+            let stake = self.stakes.get(account).unwrap_or_default();
+            self.earned_impl(&stake, self.reward_per_token())
+        }
+
+        /// Enables or disables rejecting transfers out of an account in the same
+        /// block it received tokens in, a common sniping pattern.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_same_block_guard(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.same_block_guard = enabled;
+            Ok(())
+        }
+
+        /// Exempts `account` from the same-block guard, or removes the exemption.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_same_block_exempt(&mut self, account: AccountId, exempt: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if exempt {
+                self.same_block_exempt.insert(account, &true);
+            } else {
+                self.same_block_exempt.remove(account);
+            }
+            Ok(())
+        }
+
+        /// Returns `true` if the same-block guard is enabled.
+        #[ink(message)]
+        pub fn same_block_guard(&self) -> bool {
+            self.same_block_guard
+        }
+
+        /// Sets the decimals of the token's representation on the bridged chain.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `InvalidBridgeDecimals` error if `bridge_decimals` differs from the
+        /// token decimals by more than `MAX_BRIDGE_DECIMALS_DIFF`.
+        #[ink(message)]
+        pub fn set_bridge_decimals(&mut self, bridge_decimals: u8) -> Result<()> {
+            self.ensure_owner()?;
+            if bridge_decimals.abs_diff(self.decimals) > MAX_BRIDGE_DECIMALS_DIFF {
+                return Err(Error::InvalidBridgeDecimals)
+            }
+            self.bridge_decimals = bridge_decimals;
+            Ok(())
+        }
+
+        /// Returns the decimals of the token's representation on the bridged chain.
+        #[ink(message)]
+        pub fn bridge_decimals(&self) -> u8 {
+            self.bridge_decimals
+        }
+
+        /// Converts `value` from token base units to bridged base units.
+        ///
+        /// Scaling down truncates; `bridge_dust` returns the truncated part.
+        /// Scaling up saturates at `Balance::MAX`.
+        #[ink(message)]
+        pub fn to_bridged(&self, value: Balance) -> Balance {
+            let factor = self.bridge_scale();
+            if self.bridge_decimals < self.decimals {
+                value / factor
+            } else {
+                value.saturating_mul(factor)
+            }
+        }
+
+        /// Converts `value` from bridged base units to token base units.
+        ///
+        /// Scaling down truncates, scaling up saturates at `Balance::MAX`.
+        #[ink(message)]
+        #[allow(clippy::wrong_self_convention)]
+        pub fn from_bridged(&self, value: Balance) -> Balance {
+            let factor = self.bridge_scale();
+            if self.bridge_decimals < self.decimals {
+                value.saturating_mul(factor)
+            } else {
+                value / factor
+            }
+        }
+
+        /// Returns the part of `value` lost by `to_bridged` truncation, in token
+        /// base units.
+        #[ink(message)]
+        pub fn bridge_dust(&self, value: Balance) -> Balance {
+            if self.bridge_decimals < self.decimals {
+                value % self.bridge_scale()
+            } else {
+                0
+            }
+        }
+
+        /// Locks all tokens of `account` until `timestamp`: before then `account`
+        /// can't send tokens, but can still receive them.
+        ///
+        /// A timestamp in the past unlocks the account.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `CustodyAccount` error if `account` is the contract's own account.
+        #[ink(message)]
+        pub fn lock_until(&mut self, account: AccountId, timestamp: u64) -> Result<()> {
+            self.ensure_owner()?;
+            if account == self.env().account_id() {
+                return Err(Error::CustodyAccount)
+            }
+            self.locked_until.insert(account, &timestamp);
+            Ok(())
+        }
+
+        /// Returns the timestamp until which `account` can't send tokens.
+        ///
+        /// Returns `0` if `account` was never locked.
+        #[ink(message)]
+        pub fn locked_until(&self, account: AccountId) -> u64 {
+            self.locked_until.get(account).unwrap_or_default()
+        }
+
+        /// Returns `true` if `attester` signed that `account` holds `claimed` tokens at
+        /// the current block and `claimed` matches the actual balance.
+        ///
+        /// The signed message is the Blake2x256 hash of the SCALE-encoded
+        /// `(account, claimed, block_number)`, and `attester` is the account of the
+        /// ECDSA key: the Blake2x256 hash of its compressed public key.
+        #[ink(message)]
+        pub fn verify_balance_attestation(
+            &self,
+            account: AccountId,
+            claimed: Balance,
+            signature: [u8; 65],
+            attester: AccountId,
+        ) -> bool {
+            if claimed != self.balance_of_impl(&account) {
+                return false
+            }
+            let mut message_hash = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(
+                &(account, claimed, self.env().block_number()),
+                &mut message_hash,
+            );
+            let Ok(public_key) = self.env().ecdsa_recover(&signature, &message_hash) else {
+                return false
+            };
+            let mut signer = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public_key, &mut signer);
+            AccountId::from(signer) == attester
+        }
+
+        /// Locks a further `amount` of the balance of `account`, which it can't send
+        /// until unlocked.
+        ///
+        /// The lock may exceed the current balance, in which case nothing can be sent.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `CustodyAccount` error if `account` is the contract's own account.
+        ///
+        /// Returns `Overflow` error if the locked amount would overflow.
+        #[ink(message)]
+        pub fn lock_balance(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            if account == self.env().account_id() {
+                return Err(Error::CustodyAccount)
+            }
+            let locked = self
+                .locked_balance
+                .get(account)
+                .unwrap_or_default()
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.locked_balance.insert(account, &locked);
+            Ok(())
+        }
+
+        /// Unlocks `amount` of the locked balance of `account`, down to zero.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn unlock_balance(&mut self, account: AccountId, amount: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            let locked = self
+                .locked_balance
+                .get(account)
+                .unwrap_or_default()
+                .saturating_sub(amount);
+            if locked == 0 {
+                self.locked_balance.remove(account);
+            } else {
+                self.locked_balance.insert(account, &locked);
+            }
+            Ok(())
+        }
+
+        /// Returns the locked part of the balance of `account`.
+        #[ink(message)]
+        pub fn locked_balance(&self, account: AccountId) -> Balance {
+            self.locked_balance.get(account).unwrap_or_default()
+        }
+
+        /// Returns the part of the balance of `account` it can send, which is zero if
+        /// the lock exceeds the balance.
+        #[ink(message)]
+        pub fn available_balance(&self, account: AccountId) -> Balance {
+            self.balance_of_impl(&account)
+                .saturating_sub(self.locked_balance(account))
+        }
+
+        /// Caps the amount a single transfer may move, or removes the cap with `None`.
+        ///
+        /// Minting, burning and transfers in or out of an exempt account or the
+        /// contract's own account are not capped.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_max_tx_amount(&mut self, max_tx_amount: Option<Balance>) -> Result<()> {
+            self.ensure_owner()?;
+            self.max_tx_amount = max_tx_amount;
+            Ok(())
+        }
+
+        /// Returns the largest amount a single transfer may move, if capped.
+        #[ink(message)]
+        pub fn max_tx_amount(&self) -> Option<Balance> {
+            self.max_tx_amount
+        }
+
+        /// Exempts `account` from the maximum transaction amount, or removes the
+        /// exemption.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_tx_limit_exempt(&mut self, account: AccountId, exempt: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if exempt {
+                self.tx_limit_exempt.insert(account, &true);
+            } else {
+                self.tx_limit_exempt.remove(account);
+            }
+            Ok(())
+        }
+
+        /// Returns `true` if transfers in or out of `account` are not capped.
+        #[ink(message)]
+        pub fn is_tx_limit_exempt(&self, account: AccountId) -> bool {
+            account == self.env().account_id()
+                || self.tx_limit_exempt.get(account).unwrap_or_default()
+        }
+
+        /// Sets how far short of the value an allowance may be for `transfer_from`
+        /// to still accept it, absorbing rounding dust.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_allowance_grace(&mut self, grace: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.allowance_grace = grace;
+            Ok(())
+        }
+
+        /// Returns how far short of the value an allowance may be for
+        /// `transfer_from` to still accept it.
+        #[ink(message)]
+        pub fn allowance_grace(&self) -> Balance {
+            self.allowance_grace
+        }
+
+        /// Enables or disables rejecting transfers to contracts that are not
+        /// allowlisted, protecting holders from sending tokens to contracts that
+        /// can't handle them.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_reject_contract_recipients(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.reject_contract_recipients = enabled;
+            Ok(())
+        }
+
+        /// Returns `true` if transfers to contracts that are not allowlisted are
+        /// rejected.
+        #[ink(message)]
+        pub fn reject_contract_recipients(&self) -> bool {
+            self.reject_contract_recipients
+        }
+
+        /// Allows contract `account` to receive tokens while contract recipients are
+        /// rejected, or revokes that.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_contract_recipient_allowed(
+            &mut self,
+            account: AccountId,
+            allowed: bool,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            if allowed {
+                self.contract_recipient_allowlist.insert(account, &true);
+            } else {
+                self.contract_recipient_allowlist.remove(account);
+            }
+            Ok(())
+        }
+
+        /// Returns `true` if contract `account` is allowlisted as a recipient.
+        #[ink(message)]
+        pub fn is_contract_recipient_allowed(&self, account: AccountId) -> bool {
+            self.contract_recipient_allowlist.get(account).unwrap_or_default()
+        }
+
+        /// Enables or disables restricting allowance spending to allowlisted
+        /// contracts.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_contract_spenders_only(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_owner()?;
+            self.contract_spenders_only = enabled;
+            Ok(())
+        }
+
+        /// Returns `true` if only allowlisted contracts may spend allowances.
+        #[ink(message)]
+        pub fn contract_spenders_only(&self) -> bool {
+            self.contract_spenders_only
+        }
+
+        /// Allowlists contract `spender` to spend allowances while only allowlisted
+        /// contracts may, or removes it. Accounts without code are never accepted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn set_spender_allowed(&mut self, spender: AccountId, allowed: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if allowed {
+                self.spender_allowlist.insert(spender, &true);
+            } else {
+                self.spender_allowlist.remove(spender);
+            }
+            Ok(())
+        }
+
+        /// Returns `true` if `spender` is allowlisted to spend allowances.
+        #[ink(message)]
+        pub fn is_spender_allowed(&self, spender: AccountId) -> bool {
+            self.spender_allowlist.get(spender).unwrap_or_default()
+        }
+
+        /// Excludes `account`, such as a treasury or team wallet, from the
+        /// circulating supply, or includes it again.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        #[ink(message)]
+        pub fn mark_non_circulating(&mut self, account: AccountId, flag: bool) -> Result<()> {
+            self.ensure_owner()?;
+            if flag == self.is_non_circulating(account) {
+                return Ok(())
+            }
+            let balance = self.balance_of_impl(&account);
+            if flag {
+                self.non_circulating.insert(account, &true);
+                self.non_circulating_total += balance;
+            } else {
+                self.non_circulating.remove(account);
+                self.non_circulating_total -= balance;
+            }
+            Ok(())
+        }
+
+        /// Returns `true` if the balance of `account` is not part of the
+        /// circulating supply.
+        #[ink(message)]
+        pub fn is_non_circulating(&self, account: AccountId) -> bool {
+            self.non_circulating.get(account).unwrap_or_default()
+        }
+
+        /// Returns the total supply minus the balances of the non-circulating
+        /// accounts.
+        ///
+        /// 流通量
+        #[ink(message)]
+        pub fn circulating_supply(&self) -> Balance {
+            self.total_supply - self.non_circulating_total
+        }
+
+        /// Freezes `account`: it can no longer send or burn tokens, but can still
+        /// receive them.
+        ///
+        /// On success an `AccountFrozen` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `CustodyAccount` error if `account` is the contract's own account.
+        ///
+        /// 冻结账户
+        #[ink(message)]
+        pub fn freeze(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            if account == self.env().account_id() {
+                return Err(Error::CustodyAccount)
+            }
+            self.frozen.insert(account, &true);
+            self.env().emit_event(AccountFrozen { account });
+            Ok(())
+        }
+
+        /// Unfreezes `account`, allowing it to send tokens again.
+        ///
+        /// On success an `AccountUnfrozen` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// 解冻账户
+        #[ink(message)]
+        pub fn unfreeze(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.frozen.remove(account);
+            self.env().emit_event(AccountUnfrozen { account });
+            Ok(())
+        }
+
+        /// Freezes each of `accounts`, as `freeze` does.
+        ///
+        /// On success an `AccountFrozen` event is emitted per account.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `BatchTooLarge` error if there are more than `MAX_FREEZE_BATCH`
+        /// accounts.
+        ///
+        /// Returns `CustodyAccount` error if `accounts` contains the contract's own
+        /// account, in which case no account is frozen.
+        ///
+        /// 批量冻结账户
+        #[ink(message)]
+        pub fn freeze_batch(&mut self, accounts: Vec<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+            if accounts.len() > MAX_FREEZE_BATCH {
+                return Err(Error::BatchTooLarge)
+            }
+            if accounts.contains(&self.env().account_id()) {
+                return Err(Error::CustodyAccount)
+            }
+            for account in accounts {
+                self.freeze(account)?;
+            }
+            Ok(())
+        }
+
+        /// Unfreezes each of `accounts`, as `unfreeze` does.
+        ///
+        /// On success an `AccountUnfrozen` event is emitted per account.
+        ///
+        /// # Errors
+        ///
+        /// Returns `NotOwner` error if the caller is not the owner.
+        ///
+        /// Returns `BatchTooLarge` error if there are more than `MAX_FREEZE_BATCH`
+        /// accounts.
+        ///
+        /// 批量解冻账户
+        #[ink(message)]
+        pub fn unfreeze_batch(&mut self, accounts: Vec<AccountId>) -> Result<()> {
+            self.ensure_owner()?;
+            if accounts.len() > MAX_FREEZE_BATCH {
+                return Err(Error::BatchTooLarge)
+            }
+            for account in accounts {
+                self.unfreeze(account)?;
+            }
+            Ok(())
+        }
+
+        /// Returns `true` if `account` is frozen.
+        #[ink(message)]
+        pub fn is_frozen(&self, account: AccountId) -> bool {
+            self.frozen.get(account).unwrap_or_default()
+        }
+
+        /// Returns the operational figures an admin UI shows in one call.
+        #[ink(message)]
+        pub fn admin_dashboard(&self) -> AdminInfo {
+            AdminInfo {
+                owner: self.owner,
+                total_supply: self.total_supply,
+                circulating_supply: self.circulating_supply(),
+                total_burned: self.total_burned,
+                max_tx_amount: self.max_tx_amount,
+                same_block_guard: self.same_block_guard,
+                staking_reward_rate: self.staking_reward_rate,
+                reward_pool: self.reward_pool,
+            }
+        }
+
+        /// Returns `true` if the token implements the interface `interface_id`, one
+        /// of `INTERFACE_DETECTION_ID`, `ERC20_INTERFACE_ID` and
+        /// `ERC20_METADATA_INTERFACE_ID`.
+        #[ink(message)]
+        pub fn supports_interface(&self, interface_id: [u8; 4]) -> bool {
+            [
+                INTERFACE_DETECTION_ID,
+                ERC20_INTERFACE_ID,
+                ERC20_METADATA_INTERFACE_ID,
+            ]
+            .contains(&interface_id)
+        }
+
+        /// Returns the total token supply.
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        /// Returns the stored balance cell of `owner`: `None` if it was never
+        /// written, unlike `balance_of` which reports that as `0`.
+        ///
+        /// For diagnosing storage issues, such as after an upgrade.
+        #[cfg(feature = "debug")]
+        #[ink(message)]
+        pub fn raw_balance_cell(&self, owner: AccountId) -> Option<Balance> {
+            self.balances.get(owner)
+        }
+
+        /// Returns the total amount of tokens burned since deployment.
+        ///
+        /// 累计销毁数量
+        #[ink(message)]
+        pub fn total_burned(&self) -> Balance {
+            self.total_burned
+        }
+
+        /// Returns up to the last `HISTORY_LEN` transfers, mints and burns, oldest
+        /// first.
+        ///
+        /// Zero-value transfers and transfers to self are included, matching the
+        /// `Transfer` events they emit.
+        ///
+        /// 最近转账记录
+        #[ink(message)]
+        pub fn recent_transfers(&self) -> Vec<TransferRecord> {
+            let len = self.history_recorded.min(HISTORY_LEN as u64);
+            (self.history_recorded - len..self.history_recorded)
+                .filter_map(|n| self.history.get((n % HISTORY_LEN as u64) as u32))
+                .collect()
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        ///
+        /// 返回用户余额
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balance_of_impl(&owner)
+        }
+
+        /// Returns the account balances for each of the specified `owners`.
+        ///
+        /// The balances are returned in the same order as `owners`, with `0` for
+        /// non-existent accounts.
+        ///
+        /// 批量查询用户余额
+        #[ink(message)]
+        pub fn balances_of(&self, owners: Vec<AccountId>) -> Vec<Balance> {
+            owners
+                .iter()
+                .map(|owner| self.balance_of_impl(owner))
+                .collect()
+        }
+
+        /// Returns the account balance for the specified `owner`.
+        ///
+        /// Returns `0` if the account is non-existent.
+        ///
+        /// # Note
+        /// 内部查询余额方法
+        #[inline]
+        fn balance_of_impl(&self, owner: &AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        ///
+        /// Returns `0` if no allowance has been set.
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowance_impl(&owner, &spender)
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        ///
+        /// Returns `0` if no allowance has been set.
+        ///
+        /// # Note
+        ///
+        /// Prefer to call this method over `allowance` since this
+        /// works using references which are more efficient in Wasm.
+        #[inline]
+        fn allowance_impl(&self, owner: &AccountId, spender: &AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        /// Returns the allowance `spender` can spend now, which is zero until a
+        /// scheduled allowance becomes effective.
+        ///
+        /// An allowance with auto top-up can be refilled from the remaining top-up
+        /// budget, but not beyond the balance of `owner`.
+        fn spendable_allowance(&self, owner: &AccountId, spender: &AccountId) -> Balance {
+            if self.env().block_timestamp() < self.allowance_effective_at(*owner, *spender) {
+                return 0
+            }
+            let allowance = self.allowance_impl(owner, spender);
+            let top_up = self
+                .auto_top_up
+                .get((owner, spender))
+                .unwrap_or_default()
+                .min(self.balance_of_impl(owner).saturating_sub(allowance));
+            allowance + top_up
+        }
+
+        /// Deducts `value` from the allowance of `spender` over the tokens of `owner`,
+        /// drawing any shortfall from the top-up budget, and returns what is left.
+        ///
+        /// The allowance is floored at zero, which absorbs the allowance grace.
+        fn spend_allowance(
+            &mut self,
+            owner: &AccountId,
+            spender: &AccountId,
+            value: Balance,
+        ) -> Balance {
+            let allowance = self.allowance_impl(owner, spender);
+            if value > allowance {
+                if let Some(budget) = self.auto_top_up.get((owner, spender)) {
+                    let refill = (value - allowance).min(budget);
+                    self.auto_top_up.insert((owner, spender), &(budget - refill));
+                }
+            }
+            let remaining = allowance.saturating_sub(value);
+            self.allowances.insert((owner, spender), &remaining);
+            remaining
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        ///
+        /// On success a `Transfer` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns the first failing transfer rule, in the order documented on `Error`.
+        ///
+        /// 代币转账 to other
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`,
+        /// attaching an opaque `memo` such as a deposit id.
+        ///
+        /// On success a `Transfer` event and a `TransferWithMemo` event carrying the
+        /// memo are emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `MemoTooLong` error if `memo` is longer than `MAX_MEMO_LEN` bytes.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        ///
+        /// 带备注转账
+        #[ink(message)]
+        pub fn transfer_with_memo(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            memo: Vec<u8>,
+        ) -> Result<()> {
+            if memo.len() > MAX_MEMO_LEN {
+                return Err(Error::MemoTooLong)
+            }
+            let from = self.env().caller();
+            self.transfer_from_to(&from, &to, value)?;
+            self.env().emit_event(TransferWithMemo {
+                from,
+                to,
+                value,
+                memo,
+            });
+            Ok(())
+        }
+
+        /// Splits `total` of the caller's tokens between `recipients` by their shares
+        /// in basis points, which must add up to `TOTAL_SHARES`.
+        ///
+        /// Each recipient gets `total * share / TOTAL_SHARES` rounded down; the
+        /// rounding remainder goes to the first recipient.
+        ///
+        /// On success a `BatchTransfer` event is emitted, preceded by a `Transfer`
+        /// event per recipient unless `batch_summary_only` is set.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InvalidShares` error if the shares don't add up to `TOTAL_SHARES`.
+        ///
+        /// Returns `Overflow` error if `total * share` overflows.
+        ///
+        /// Returns `InsufficientBalance` error if the caller holds less than `total`.
+        ///
+        /// 按份额分配转账
+        #[ink(message)]
+        pub fn transfer_weighted(
+            &mut self,
+            total: Balance,
+            recipients: Vec<(AccountId, u16)>,
+        ) -> Result<()> {
+            let shares: u32 = recipients.iter().map(|(_, share)| u32::from(*share)).sum();
+            if shares != TOTAL_SHARES {
+                return Err(Error::InvalidShares)
+            }
+            let mut amounts = recipients
+                .iter()
+                .map(|(to, share)| {
+                    let value = mul_div(total, Balance::from(*share), Balance::from(TOTAL_SHARES))?;
+                    Ok((*to, value))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let distributed: Balance = amounts.iter().map(|(_, value)| value).sum();
+            // shares add up to the whole, so this is less than one unit per recipient
+            amounts[0].1 += total - distributed;
+            let from = self.env().caller();
+            for (to, value) in &amounts {
+                if self.batch_summary_only {
+                    self.transfer_balance(&from, to, *value)?;
+                } else {
+                    self.transfer_from_to(&from, to, *value)?;
+                }
+            }
+            self.env().emit_event(BatchTransfer {
+                from: Some(from),
+                count: amounts.len() as u32,
+                total,
+            });
+            Ok(())
+        }
+
+        /// Allows `spender` to withdraw from the caller's account multiple times, up to
+        /// the `value` amount.
+        ///
+        /// If this function is called again it overwrites the current allowance with
+        /// `value`.
+        ///
+        /// 授予转账
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.insert((&owner, &spender), &value);
+            self.allowance_effective_at.remove((&owner, &spender));
+            self.auto_top_up.remove((&owner, &spender));
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Like `approve`, but `spender` can't spend the allowance before the
+        /// `effective_at` timestamp.
+        ///
+        /// On success an `Approval` event is emitted.
+        #[ink(message)]
+        pub fn approve_at(
+            &mut self,
+            spender: AccountId,
+            value: Balance,
+            effective_at: u64,
+        ) -> Result<()> {
+            self.approve(spender, value)?;
+            let owner = self.env().caller();
+            self.allowance_effective_at.insert((&owner, &spender), &effective_at);
+            Ok(())
+        }
+
+        /// Returns the timestamp from which `spender` can spend its allowance over
+        /// the tokens of `owner`, `0` if it was not scheduled.
+        #[ink(message)]
+        pub fn allowance_effective_at(&self, owner: AccountId, spender: AccountId) -> u64 {
+            self.allowance_effective_at
+                .get((&owner, &spender))
+                .unwrap_or_default()
+        }
+
+        /// Gives `spender` a top-up budget of `cap` tokens: whenever its allowance over
+        /// the caller's tokens runs short, `transfer_from` refills the shortfall from
+        /// the budget until it is used up. `None` removes the budget.
+        ///
+        /// A refill never exceeds the caller's balance. `approve` and
+        /// `revoke_approval` remove the budget.
+        #[ink(message)]
+        pub fn set_auto_top_up(
+            &mut self,
+            spender: AccountId,
+            cap: Option<Balance>,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            if let Some(cap) = cap {
+                self.auto_top_up.insert((&owner, &spender), &cap);
+            } else {
+                self.auto_top_up.remove((&owner, &spender));
+            }
+            Ok(())
+        }
+
+        /// Returns the top-up budget left for the allowance of `spender` over the
+        /// tokens of `owner`, if auto top-up is enabled.
+        #[ink(message)]
+        pub fn auto_top_up(&self, owner: AccountId, spender: AccountId) -> Option<Balance> {
+            self.auto_top_up.get((&owner, &spender))
+        }
+
+        /// Revokes the allowance of `spender` over the caller's tokens, removing the
+        /// entry from storage rather than storing zero.
+        ///
+        /// On success an `Approval` event with `value: 0` and an `ApprovalRevoked`
+        /// event are emitted.
+        ///
+        /// 撤销授权
+        #[ink(message)]
+        pub fn revoke_approval(&mut self, spender: AccountId) -> Result<()> {
+            let owner = self.env().caller();
+            self.allowances.remove((&owner, &spender));
+            self.allowance_effective_at.remove((&owner, &spender));
+            self.auto_top_up.remove((&owner, &spender));
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: 0,
+            });
+            self.env().emit_event(ApprovalRevoked { owner, spender });
+            Ok(())
+        }
+
+        /// Increases the allowance of `spender` over the caller's tokens by `delta`.
+        ///
+        /// On success an `Approval` event with the new allowance is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Overflow` error if the new allowance would overflow, leaving
+        /// the stored allowance unchanged.
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            let value = self
+                .allowance_impl(&owner, &spender)
+                .checked_add(delta)
+                .ok_or(Error::Overflow)?;
+            self.allowances.insert((&owner, &spender), &value);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Adjusts the caller's allowances in one call: each `(spender, delta)` of
+        /// `adjustments` increases the allowance of `spender` by a positive `delta`
+        /// and decreases it by a negative one.
+        ///
+        /// The whole batch is checked before anything is written, so either every
+        /// adjustment is applied or none is. Repeated spenders are applied in order.
+        ///
+        /// On success an `Approval` event with the new allowance is emitted per
+        /// adjustment.
+        ///
+        /// # Errors
+        ///
+        /// Returns `Overflow` error if an allowance would overflow.
+        ///
+        /// Returns `InsufficientAllowance` error if a decrease exceeds the allowance.
+        #[ink(message)]
+        pub fn adjust_allowances(&mut self, adjustments: Vec<(AccountId, i128)>) -> Result<()> {
+            let owner = self.env().caller();
+            let mut updated: Vec<(AccountId, Balance)> = Vec::with_capacity(adjustments.len());
+            for (spender, delta) in adjustments {
+                let current = updated
+                    .iter()
+                    .rev()
+                    .find(|(updated_spender, _)| *updated_spender == spender)
+                    .map(|(_, value)| *value)
+                    .unwrap_or_else(|| self.allowance_impl(&owner, &spender));
+                let value = if delta >= 0 {
+                    current
+                        .checked_add(delta.unsigned_abs())
+                        .ok_or(Error::Overflow)?
+                } else {
+                    current.checked_sub(delta.unsigned_abs()).ok_or(
+                        Error::InsufficientAllowance {
+                            available: current,
+                            requested: delta.unsigned_abs(),
+                        },
+                    )?
+                };
+                updated.push((spender, value));
+            }
+            for (spender, value) in updated {
+                self.allowances.insert((&owner, &spender), &value);
+                self.env().emit_event(Approval {
+                    owner,
+                    spender,
+                    value,
+                });
+            }
+            Ok(())
+        }
+
+        /// Sets the allowance of `spender` to `new_value`, but only if it currently
+        /// equals `expected_current`.
+        ///
+        /// This lets callers change an allowance without racing a `transfer_from`
+        /// that spends the old one in between.
+        ///
+        /// On success an `Approval` event is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `AllowanceMismatch` error if the current allowance is not
+        /// `expected_current`.
+        #[ink(message)]
+        pub fn safe_approve(
+            &mut self,
+            spender: AccountId,
+            expected_current: Balance,
+            new_value: Balance,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            if self.allowance_impl(&owner, &spender) != expected_current {
+                return Err(Error::AllowanceMismatch)
+            }
+            self.allowances.insert((&owner, &spender), &new_value);
+            self.allowance_effective_at.remove((&owner, &spender));
+            self.auto_top_up.remove((&owner, &spender));
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: new_value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+        ///
+        /// This can be used to allow a contract to transfer tokens on ones behalf and/or
+        /// to charge fees in sub-currencies, for example.
+        ///
+        /// A non-zero allowance short of `value` by at most the allowance grace is
+        /// treated as just enough and zeroed, so the grace is used at most once per
+        /// approval. Allowances scheduled with `approve_at` count
+        /// as zero until they become effective, and allowances with auto top-up are
+        /// refilled first.
+        ///
+        /// On success a `Transfer` event, an `Approval` event with the remaining
+        /// allowance and a `DelegatedTransfer` event recording the caller as spender
+        /// are emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `SpenderNotContract` error if only allowlisted contracts may spend
+        /// allowances and the caller is not one.
+        ///
+        /// Returns `InsufficientAllowance` error if there are not enough tokens allowed
+        /// for the caller to withdraw from `from`.
+        ///
+        /// 授予转账，提币出来
+        #[ink(message)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            self.ensure_spender_allowed(&caller)?;
+            // 检查是否授予转账
+            let allowance = self.spendable_allowance(&from, &caller);
+            if allowance < value && (allowance == 0 || value - allowance > self.allowance_grace) {
+                return Err(Error::InsufficientAllowance {
+                    available: allowance,
+                    requested: value,
+                })
+            }
+            // 转账代币
+            self.transfer_from_to(&from, &to, value)?;
+            let remaining = self.spend_allowance(&from, &caller, value);
+            self.env().emit_event(Approval {
+                owner: from,
+                spender: caller,
+                value: remaining,
+            });
+            self.env().emit_event(DelegatedTransfer {
+                spender: caller,
+                from,
+                to,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` tokens from the caller to `to` on behalf of `sponsor`,
+        /// who pays a `sponsor_fee` to the contract owner for relaying it.
+        ///
+        /// The fee is taken from the allowance `sponsor` gave the caller and held by
+        /// the contract until the owner collects it with `claim_pending`, so a push
+        /// to an owner that cannot receive tokens never blocks the transfer. On success
+        /// a `Transfer` event for the transfer, a `Transfer` event for the fee and an
+        /// `Approval` event with the remaining allowance are emitted, followed by a
+        /// `SponsoredTransfer` event.
+        ///
+        /// # Errors
+        ///
+        /// Returns `ZeroAmount` error if `sponsor_fee` is zero, since nothing would
+        /// show that `sponsor` agreed to the sponsorship.
+        ///
+        /// Returns `SpenderNotContract` error if only allowlisted contracts may spend
+        /// allowances and the caller is not one.
+        ///
+        /// Returns `InsufficientAllowance` error if `sponsor` allowed the caller less
+        /// than `sponsor_fee`.
+        ///
+        /// Returns `Overflow` error if the fees pending for the owner would overflow.
+        ///
+        /// Returns the first failing transfer rule of either transfer.
+        #[ink(message)]
+        pub fn transfer_sponsored(
+            &mut self,
+            sponsor: AccountId,
+            to: AccountId,
+            value: Balance,
+            sponsor_fee: Balance,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            let contract = self.env().account_id();
+            if sponsor_fee == 0 {
+                return Err(Error::ZeroAmount)
+            }
+            self.ensure_spender_allowed(&caller)?;
+            let allowance = self.spendable_allowance(&sponsor, &caller);
+            if allowance < sponsor_fee {
+                return Err(Error::InsufficientAllowance {
+                    available: allowance,
+                    requested: sponsor_fee,
+                })
+            }
+            let pending = self
+                .pending_of(self.owner)
+                .checked_add(sponsor_fee)
+                .ok_or(Error::Overflow)?;
+            self.check_transfer_allowed(&sponsor, &contract, sponsor_fee)?;
+            self.transfer_from_to(&caller, &to, value)?;
+            self.transfer_from_to(&sponsor, &contract, sponsor_fee)?;
+            self.pending_withdrawals.insert(self.owner, &pending);
+            let remaining = self.spend_allowance(&sponsor, &caller, sponsor_fee);
+            self.env().emit_event(Approval {
+                owner: sponsor,
+                spender: caller,
+                value: remaining,
+            });
+            self.env().emit_event(SponsoredTransfer {
+                sponsor,
+                from: caller,
+                to,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Transfers `value` amount of tokens from the caller's account to account `to`.
+        ///
+        /// On success a `Transfer` event is emitted. Zero-value transfers and
+        /// transfers to self are allowed, emit the event and are recorded in the
+        /// history, but leave balances untouched.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        fn transfer_from_to(
+            &mut self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.transfer_balance(from, to, value)?;
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: Some(*to),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Moves `value` tokens from `from` to `to` like `transfer_from_to`, without
+        /// emitting the `Transfer` event.
+        fn transfer_balance(
+            &mut self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            self.check_transfer_allowed(from, to, value)?;
+
+            if value == 0 || from == to {
+                self.record_transfer(Some(*from), Some(*to), value);
+                return Ok(())
+            }
+
+            let from_balance = self.balance_of_impl(from);
+            self.balances.insert(from, &(from_balance - value));
+            let to_balance = self.balance_of_impl(to);
+            self.balances.insert(to, &(to_balance + value));
+            self.record_receipt(to);
+            self.correct_dividends(from, value, true);
+            self.correct_dividends(to, value, false);
+            self.track_non_circulating(from, value, true);
+            self.track_non_circulating(to, value, false);
+            self.record_transfer(Some(*from), Some(*to), value);
+            Ok(())
+        }
+
+        /// Records that `to` received tokens in the current block, for the
+        /// same-block guard.
+        fn record_receipt(&mut self, to: &AccountId) {
+            if self.same_block_guard {
+                self.last_received_block.insert(to, &self.env().block_number());
+            }
+        }
+
+        /// Records a balance change in the history ring buffer, overwriting the
+        /// oldest record once it is full.
+        fn record_transfer(
+            &mut self,
+            from: Option<AccountId>,
+            to: Option<AccountId>,
+            value: Balance,
+        ) {
+            let slot = (self.history_recorded % HISTORY_LEN as u64) as u32;
+            self.history.insert(slot, &(from, to, value, self.env().block_number()));
+            self.history_recorded += 1;
+        }
+
+        /// Keeps the dividends `account` has accrued unchanged after its balance
+        /// decreased (`decreased == true`) or increased by `value`.
+        ///
+        /// Must be called on every balance change outside of a distribution.
+        fn correct_dividends(&mut self, account: &AccountId, value: Balance, decreased: bool) {
+            if self.magnified_dividend_per_share == 0 {
+                return
+            }
+            let magnified = Self::magnified_dividends(self.magnified_dividend_per_share, value);
+            let correction = self.dividend_corrections.get(account).unwrap_or_default();
+            let correction = if decreased {
+                correction.checked_add(magnified)
+            } else {
+                correction.checked_sub(magnified)
+            };
+            let correction = correction.expect("dividend corrections out of range");
+            self.dividend_corrections.insert(account, &correction);
+        }
+
+        /// Returns `true` if `per_share` magnified dividends on `supply` tokens fit
+        /// in an `i128`.
+        ///
+        /// `distribute_dividends` and `mint_balance` keep this true for the total
+        /// supply, so the dividends of any balance or transfer fit as well and the
+        /// corrections stay exact.
+        fn dividends_fit(per_share: u128, supply: Balance) -> bool {
+            matches!(
+                per_share.checked_mul(supply),
+                Some(magnified) if magnified <= i128::MAX as u128
+            )
+        }
+
+        /// Returns the magnified dividends `per_share` implies for `value` tokens.
+        ///
+        /// `value` must not exceed the total supply; see `dividends_fit`.
+        fn magnified_dividends(per_share: u128, value: Balance) -> i128 {
+            per_share
+                .checked_mul(value)
+                .and_then(|magnified| i128::try_from(magnified).ok())
+                .expect("dividends exceed the total supply bound")
+        }
+
+        /// Keeps `non_circulating_total` in sync after the balance of `account`
+        /// decreased (`decreased == true`) or increased by `value`.
+        ///
+        /// Must be called on every balance change.
+        fn track_non_circulating(&mut self, account: &AccountId, value: Balance, decreased: bool) {
+            if !self.is_non_circulating(*account) {
+                return
+            }
+            if decreased {
+                self.non_circulating_total -= value;
+            } else {
+                self.non_circulating_total += value;
+            }
+        }
+
+        /// Creates `value` tokens for `to` and increases the total supply.
+        ///
+        /// # Errors
+        ///
+        /// Returns `SupplyFixed` error if the supply is finalized.
+        ///
+        /// Returns `Overflow` error if the total supply would overflow or grow past
+        /// what the dividend accounting can represent.
+        fn mint_impl(&mut self, to: &AccountId, value: Balance) -> Result<()> {
+            self.mint_balance(to, value)?;
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(*to),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Credits `value` new tokens to `to` like `mint_impl`, without emitting
+        /// the `Transfer` event.
+        fn mint_balance(&mut self, to: &AccountId, value: Balance) -> Result<()> {
+            if self.supply_fixed {
+                return Err(Error::SupplyFixed)
+            }
+            let total_supply = self
+                .total_supply
+                .checked_add(value)
+                .filter(|supply| Self::dividends_fit(self.magnified_dividend_per_share, *supply))
+                .ok_or(Error::Overflow)?;
+            // the balance can never exceed the total supply
+            let to_balance = self.balance_of_impl(to) + value;
+            self.total_supply = total_supply;
+            self.balances.insert(to, &to_balance);
+            self.record_receipt(to);
+            self.correct_dividends(to, value, false);
+            self.track_non_circulating(to, value, false);
+            self.record_transfer(None, Some(*to), value);
+            Ok(())
+        }
+
+        /// Destroys `value` tokens of `from`, decreases the total supply and adds
+        /// them to the burned total.
+        ///
+        /// # Errors
+        ///
+        /// Returns `AccountFrozen` error if `from` is frozen.
+        ///
+        /// Returns `TokensLocked` error if `from` is locked until a later timestamp.
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the `from` account balance.
+        ///
+        /// Returns `InsufficientUnlockedBalance` error if less than `value` of it is
+        /// unlocked.
+        ///
+        /// Returns `Overflow` error if the burned total would overflow.
+        fn burn_impl(&mut self, from: &AccountId, value: Balance) -> Result<()> {
+            if self.frozen.get(from).unwrap_or_default() {
+                return Err(Error::AccountFrozen)
+            }
+            if self.env().block_timestamp() < self.locked_until.get(from).unwrap_or_default() {
+                return Err(Error::TokensLocked)
+            }
+            let from_balance = self.balance_of_impl(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance {
+                    available: from_balance,
+                    requested: value,
+                })
+            }
+            if self.available_balance(*from) < value {
+                return Err(Error::InsufficientUnlockedBalance)
+            }
+            let total_burned = self.total_burned.checked_add(value).ok_or(Error::Overflow)?;
+            self.balances.insert(from, &(from_balance - value));
+            self.total_supply -= value;
+            self.total_burned = total_burned;
+            self.correct_dividends(from, value, true);
+            self.track_non_circulating(from, value, true);
+            self.record_transfer(Some(*from), None, value);
+            self.env().emit_event(Transfer {
+                from: Some(*from),
+                to: None,
+                value,
+            });
+            Ok(())
+        }
+
+        /// Returns the amount of `schedule` vested at the current block timestamp.
+        fn vested_amount_impl(&self, schedule: &VestingSchedule) -> Balance {
+            let now = self.env().block_timestamp();
+            if now < schedule.start {
+                return 0
+            }
+            let elapsed = now - schedule.start;
+            if elapsed >= schedule.duration {
+                return schedule.total
+            }
+            // split `total * elapsed / duration` so the product can't overflow
+            let duration = Balance::from(schedule.duration);
+            let elapsed = Balance::from(elapsed);
+            schedule.total / duration * elapsed
+                + schedule.total % duration * elapsed / duration
+        }
+
+        /// Computes the domain separator of a token named `name` deployed at the
+        /// current contract account.
+        fn compute_domain_separator(name: &Option<String>) -> [u8; 32] {
+            let mut output = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Keccak256, _>(
+                &(name, DOMAIN_VERSION, Self::env().account_id()),
+                &mut output,
+            );
+            output
+        }
+
+        /// Returns the rewards accrued per staked token up to the current block
+        /// timestamp, scaled by `REWARD_MAGNITUDE`.
+        fn reward_per_token(&self) -> u128 {
+            if self.total_staked == 0 {
+                return self.reward_per_token_stored
+            }
+            let elapsed = self
+                .env()
+                .block_timestamp()
+                .saturating_sub(self.reward_updated_at);
+            let accrued = Balance::from(elapsed)
+                .saturating_mul(self.staking_reward_rate)
+                .saturating_mul(REWARD_MAGNITUDE)
+                / self.total_staked;
+            self.reward_per_token_stored.saturating_add(accrued)
+        }
+
+        /// Returns the rewards of `stake` accrued up to `reward_per_token`.
+        ///
+        /// Nothing accrues while the stake's rewards are paused.
+        fn earned_impl(&self, stake: &StakeInfo, reward_per_token: u128) -> Balance {
+            if stake.rewards_paused {
+                return stake.rewards
+            }
+            let accrued = stake
+                .staked
+                .saturating_mul(reward_per_token - stake.reward_per_token_paid)
+                / REWARD_MAGNITUDE;
+            stake.rewards.saturating_add(accrued)
+        }
+
+        /// Brings the reward accumulator and the rewards of `staker` up to date and
+        /// returns the staker's position.
+        ///
+        /// Must be called before any change to the staked amounts or the rate.
+        fn update_reward(&mut self, staker: &AccountId) -> StakeInfo {
+            let reward_per_token = self.reward_per_token();
+            self.reward_per_token_stored = reward_per_token;
+            self.reward_updated_at = self.env().block_timestamp();
+            let mut stake = self.stakes.get(staker).unwrap_or_default();
+            stake.rewards = self.earned_impl(&stake, reward_per_token);
+            stake.reward_per_token_paid = reward_per_token;
+            stake
+        }
+
+        /// Returns the factor between token and bridged base units.
+        fn bridge_scale(&self) -> Balance {
+            // bounded by `MAX_BRIDGE_DECIMALS_DIFF`, so this can't overflow
+            10u128.pow(self.bridge_decimals.abs_diff(self.decimals).into())
+        }
+
+        /// Returns `NotOwner` error if the caller is not the owner.
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner)
+            }
+            Ok(())
+        }
+
+        /// Returns `SpenderNotContract` error if only allowlisted contracts may spend
+        /// allowances and `spender` is not one.
+        fn ensure_spender_allowed(&self, spender: &AccountId) -> Result<()> {
+            if self.contract_spenders_only
+                && !(self.spender_allowlist.get(spender).unwrap_or_default()
+                    && self.env().is_contract(spender))
+            {
+                return Err(Error::SpenderNotContract)
+            }
+            Ok(())
+        }
+
+        /// Checks every precondition for moving `value` tokens from `from` to `to`.
+        ///
+        /// All transfer rules live here so that `transfer`, `transfer_from` and any
+        /// other path through `transfer_from_to` enforce the same set.
+        ///
+        /// # Errors
+        ///
+        /// Returns the error of the first failing condition, in the order documented
+        /// on `Error`:
+        ///
+        /// - `AccountFrozen` if `from` is frozen; `to` may be frozen.
+        /// - `SameBlockTransfer` if the same-block guard is on and `from` received
+        ///   tokens in the current block, unless `from` is exempt or the contract.
+        /// - `TokensLocked` if `from` is locked until a later timestamp.
+        /// - `ContractRecipientRejected` if contract recipients are rejected and `to`
+        ///   is a contract other than this one that is not allowlisted.
+        /// - `ExceedsMaxTx` if `value` exceeds the maximum transaction amount and
+        ///   neither `from` nor `to` is exempt.
+        /// - `InsufficientBalance` if `from` holds less than `value`.
+        /// - `InsufficientUnlockedBalance` if less than `value` of it is unlocked.
+        fn check_transfer_allowed(
+            &self,
+            from: &AccountId,
+            to: &AccountId,
+            value: Balance,
+        ) -> Result<()> {
+            if self.frozen.get(from).unwrap_or_default() {
+                return Err(Error::AccountFrozen)
+            }
+            if self.same_block_guard
+                && *from != self.env().account_id()
+                && !self.same_block_exempt.get(from).unwrap_or_default()
+                && self.last_received_block.get(from) == Some(self.env().block_number())
+            {
+                return Err(Error::SameBlockTransfer)
+            }
+            if self.env().block_timestamp() < self.locked_until.get(from).unwrap_or_default() {
+                return Err(Error::TokensLocked)
+            }
+            if self.reject_contract_recipients
+                && *to != self.env().account_id()
+                && !self.contract_recipient_allowlist.get(to).unwrap_or_default()
+                && self.env().is_contract(to)
+            {
+                return Err(Error::ContractRecipientRejected)
+            }
+            if let Some(max_tx_amount) = self.max_tx_amount {
+                if value > max_tx_amount
+                    && !self.is_tx_limit_exempt(*from)
+                    && !self.is_tx_limit_exempt(*to)
+                {
+                    return Err(Error::ExceedsMaxTx)
+                }
+            }
+            let available = self.balance_of_impl(from);
+            if available < value {
+                return Err(Error::InsufficientBalance { available, requested: value })
+            }
+            if self.available_balance(*from) < value {
+                return Err(Error::InsufficientUnlockedBalance)
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Event = <Erc20 as ::ink::reflect::ContractEventBase>::Type;
+
+        /// Gives the contract an account of its own; by default the callee is Alice.
+        ///
+        /// Must be called before the contract is constructed, since the off-chain
+        /// environment keys contract storage by callee.
+        fn set_contract_account() -> AccountId {
+            let contract = AccountId::from([0x42; 32]);
+            ink::env::test::set_callee::<ink::env::DefaultEnvironment>(contract);
+            contract
+        }
+
+        /// The default constructor does its job.
+        #[ink::test]
+        fn constructor_works() {
+            // Constructor works.
+            let _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            
+            assert_eq!(_erc20.total_supply(), 10000);
+            assert_eq!(_erc20.balance_of(accounts.alice), 10000);
+
+            let eniteed_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = &eniteed_events[0];
+
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+
+            match decoded {
+                Event::Transfer(Transfer { from, to, value }) => {
+                    assert!(from.is_none(),"mint from error");
+                    assert_eq!(to, Some(accounts.alice),"mint to error");
+                    assert_eq!(value, 10000,"mint value error");
+                },
+                _ => panic!("match invalid event")
+            }
+        }
+        #[ink::test]
+        fn metadata_constructor_works() {
+            let _erc20 = Erc20::new_with_metadata(
+                10000,
+                Some(String::from("Token")),
+                Some(String::from("TKN")),
+                18,
+            );
+
+            assert_eq!(_erc20.token_name(), Some(String::from("Token")));
+            assert_eq!(_erc20.token_symbol(), Some(String::from("TKN")));
+            assert_eq!(_erc20.token_decimals(), 18);
+            assert_eq!(_erc20.total_supply(), 10000);
+        }
+        #[ink::test]
+        fn metadata_works() {
+            let _erc20 = Erc20::new_with_metadata(
+                10000,
+                Some(String::from("Token")),
+                Some(String::from("TKN")),
+                18,
+            );
+
+            assert_eq!(
+                _erc20.metadata(),
+                (Some(String::from("Token")), Some(String::from("TKN")), 18, 10000)
+            );
+        }
+        #[ink::test]
+        fn display_decimals_override_works() {
+            let mut _erc20 = Erc20::new_with_metadata(10000, None, None, 18);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(_erc20.set_display_decimals(Some(6)).is_ok());
+            assert_eq!(_erc20.token_decimals(), 6);
+
+            // transfers are still denominated in base units
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 100);
+            assert_eq!(_erc20.balance_of(accounts.alice), 9900);
+
+            assert!(_erc20.set_display_decimals(None).is_ok());
+            assert_eq!(_erc20.token_decimals(), 18);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.set_display_decimals(Some(2)), Err(Error::NotOwner));
+        }
+        #[ink::test]
+        fn domain_separator_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _erc20 = Erc20::new_with_metadata(0, Some(String::from("Token")), None, 18);
+            let separator = _erc20.domain_separator();
+
+            assert_eq!(_erc20.domain_separator(), separator);
+            assert_ne!(separator, [0u8; 32]);
+            assert_eq!(_erc20.nonces(accounts.alice), 0);
+
+            let renamed = Erc20::new_with_metadata(0, Some(String::from("Other")), None, 18);
+            assert_ne!(renamed.domain_separator(), separator);
+        }
+        #[ink::test]
+        fn set_code_hash_requires_owner() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+
+            assert_eq!(
+                _erc20.set_code_hash(Hash::from([0x01; 32])),
+                Err(Error::NotOwner)
+            );
+        }
+        #[ink::test]
+        fn bridge_decimals_scaling_works() {
+            let mut _erc20 = Erc20::new_with_metadata(0, None, None, 18);
+            assert_eq!(_erc20.bridge_decimals(), 18);
+            assert_eq!(_erc20.to_bridged(1234), 1234);
+
+            assert!(_erc20.set_bridge_decimals(6).is_ok());
+            let value = 1_234_567_890_123_456_789;
+            let bridged = _erc20.to_bridged(value);
+            assert_eq!(bridged, 1_234_567);
+            assert_eq!(_erc20.bridge_dust(value), 890_123_456_789);
+            assert_eq!(_erc20.from_bridged(bridged), 1_234_567_000_000_000_000);
+            assert_eq!(_erc20.from_bridged(bridged) + _erc20.bridge_dust(value), value);
+
+            // scaling up is lossless
+            assert!(_erc20.set_bridge_decimals(20).is_ok());
+            assert_eq!(_erc20.to_bridged(5), 500);
+            assert_eq!(_erc20.from_bridged(500), 5);
+            assert_eq!(_erc20.bridge_dust(5), 0);
+
+            assert_eq!(_erc20.set_bridge_decimals(57), Err(Error::InvalidBridgeDecimals));
+        }
+        /// Signs that `account` holds `claimed` tokens at the current block, returning
+        /// the signature and the signing attester's account.
+        fn sign_attestation(account: AccountId, claimed: Balance) -> ([u8; 65], AccountId) {
+            let secret_key = secp256k1::SecretKey::from_slice(&[0x2a; 32]).expect("invalid secret key");
+            let public_key = secp256k1::PublicKey::from_secret_key(secp256k1::SECP256K1, &secret_key);
+            let mut attester = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&public_key.serialize(), &mut attester);
+
+            let block = ink::env::block_number::<ink::env::DefaultEnvironment>();
+            let mut message_hash = [0u8; 32];
+            ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(account, claimed, block), &mut message_hash);
+            let message = secp256k1::Message::from_slice(&message_hash).expect("invalid message");
+            let (recovery_id, compact) = secp256k1::SECP256K1
+                .sign_ecdsa_recoverable(&message, &secret_key)
+                .serialize_compact();
+            let mut signature = [0u8; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            (signature, AccountId::from(attester))
+        }
+        #[ink::test]
+        fn balance_attestation_works() {
+            let _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let (signature, attester) = sign_attestation(accounts.alice, 10000);
+            assert!(_erc20.verify_balance_attestation(accounts.alice, 10000, signature, attester));
+            assert!(!_erc20.verify_balance_attestation(accounts.alice, 10000, signature, accounts.bob));
+        }
+        #[ink::test]
+        fn mismatched_balance_attestation_fails() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let (signature, attester) = sign_attestation(accounts.alice, 10000);
+            assert!(_erc20.transfer(accounts.bob, 1).is_ok());
+            assert!(!_erc20.verify_balance_attestation(accounts.alice, 10000, signature, attester));
+
+            // signed for another amount than claimed
+            assert!(!_erc20.verify_balance_attestation(accounts.alice, 9999, signature, attester));
+        }
+        #[ink::test]
+        fn transfer_should_work() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let res = _erc20.transfer(accounts.bob,12);
+
+            assert!(res.is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice),10000-12);
+            assert_eq!(_erc20.balance_of(accounts.bob),12);
+        }   
+        #[ink::test]
+        fn invalid_transfer_should_work() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let res = _erc20.transfer(accounts.bob,12);
+
+            assert!(res.is_err());
+            assert_eq!(res,Err(Error::InsufficientBalance { available: 0, requested: 12 }));
+        }
+        #[ink::test]
+        fn self_transfer_leaves_balance_unchanged() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(_erc20.transfer(accounts.alice, 100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 10000);
+            assert_eq!(_erc20.total_supply(), 10000);
+            // self-transfers still require the balance
+            assert_eq!(
+                _erc20.transfer(accounts.alice, 10001),
+                Err(Error::InsufficientBalance { available: 10000, requested: 10001 })
+            );
+        }
+        #[ink::test]
+        fn zero_transfer_leaves_balances_unchanged() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(_erc20.transfer(accounts.bob, 0).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 10000);
+            assert_eq!(_erc20.balance_of(accounts.bob), 0);
+            // the zero-value transfer is still recorded
+            assert_eq!(ink::env::test::recorded_events().count(), 2);
+        }
+        #[ink::test]
+        fn same_block_guard_works() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_same_block_guard(true).is_ok());
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.transfer(accounts.charlie, 10), Err(Error::SameBlockTransfer));
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.charlie, 10).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.charlie), 10);
+        }
+        #[ink::test]
+        fn same_block_guard_exempts_accounts() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_same_block_guard(true).is_ok());
+            assert!(_erc20.set_same_block_exempt(accounts.bob, true).is_ok());
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer(accounts.charlie, 10).is_ok());
+
+            // without the guard receiving and sending in one block is fine
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_same_block_guard(false).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(_erc20.transfer(accounts.django, 10).is_ok());
+        }
+        #[ink::test]
+        fn time_lock_works() {
+            set_contract_account();
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert!(_erc20.lock_until(accounts.alice, 5000).is_ok());
+            assert_eq!(_erc20.locked_until(accounts.alice), 5000);
+
+            assert_eq!(_erc20.transfer(accounts.bob, 10), Err(Error::TokensLocked));
+
+            // receiving is still allowed
+            assert!(_erc20.mint(accounts.alice, 10).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.lock_until(accounts.bob, 0), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert!(_erc20.transfer(accounts.bob, 10).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 10);
+        }
+        #[ink::test]
+        fn frozen_account_can_only_receive() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert!(_erc20.freeze(accounts.bob).is_ok());
+            assert!(_erc20.is_frozen(accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.transfer(accounts.charlie, 10), Err(Error::AccountFrozen));
+            assert_eq!(_erc20.unfreeze(accounts.bob), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 200);
+
+            assert!(_erc20.unfreeze(accounts.bob).is_ok());
+            assert!(!_erc20.is_frozen(accounts.bob));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer(accounts.charlie, 10).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.charlie), 10);
+        }
+        #[ink::test]
+        fn partial_lock_works() {
+            set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.lock_balance(accounts.alice, 600).is_ok());
+            assert_eq!(_erc20.available_balance(accounts.alice), 400);
+
+            assert_eq!(_erc20.transfer(accounts.bob, 401), Err(Error::InsufficientUnlockedBalance));
+            assert!(_erc20.transfer(accounts.bob, 400).is_ok());
+            assert_eq!(_erc20.available_balance(accounts.alice), 0);
+            assert_eq!(_erc20.transfer(accounts.bob, 1), Err(Error::InsufficientUnlockedBalance));
+
+            // a lock larger than the balance leaves nothing spendable
+            assert!(_erc20.lock_balance(accounts.alice, 1000).is_ok());
+            assert_eq!(_erc20.available_balance(accounts.alice), 0);
+
+            assert!(_erc20.unlock_balance(accounts.alice, Balance::MAX).is_ok());
+            assert_eq!(_erc20.locked_balance(accounts.alice), 0);
+            assert!(_erc20.transfer(accounts.bob, 600).is_ok());
+        }
+        #[ink::test]
+        fn max_tx_amount_works() {
+            set_contract_account();
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            // no cap behaves as before
+            assert!(_erc20.transfer(accounts.bob, 5000).is_ok());
+
+            assert!(_erc20.set_max_tx_amount(Some(100)).is_ok());
+            assert_eq!(_erc20.transfer(accounts.bob, 101), Err(Error::ExceedsMaxTx));
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            // minting is not capped
+            assert!(_erc20.mint(accounts.bob, 1000).is_ok());
+
+            assert!(_erc20.set_tx_limit_exempt(accounts.charlie, true).is_ok());
+            assert!(_erc20.transfer(accounts.charlie, 1000).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(_erc20.transfer(accounts.django, 500).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(_erc20.transfer(accounts.eve, 101), Err(Error::ExceedsMaxTx));
+            assert_eq!(_erc20.set_max_tx_amount(None), Err(Error::NotOwner));
+        }
+        #[ink::test]
+        fn transfer_errors_follow_documented_precedence() {
+            set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_same_block_guard(true).is_ok());
+            assert!(_erc20.transfer(accounts.bob, 50).is_ok());
+            assert!(_erc20.freeze(accounts.bob).is_ok());
+            assert!(_erc20.lock_until(accounts.bob, 100).is_ok());
+            ink::env::test::set_contract::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(_erc20.set_reject_contract_recipients(true).is_ok());
+            assert!(_erc20.set_max_tx_amount(Some(60)).is_ok());
+            assert!(_erc20.lock_balance(accounts.bob, 30).is_ok());
+
+            // every rule is broken at once; lift them one by one
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.transfer(accounts.charlie, 70), Err(Error::AccountFrozen));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.unfreeze(accounts.bob).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.transfer(accounts.charlie, 70), Err(Error::SameBlockTransfer));
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            assert_eq!(_erc20.transfer(accounts.charlie, 70), Err(Error::TokensLocked));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.lock_until(accounts.bob, 0).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer(accounts.charlie, 70),
+                Err(Error::ContractRecipientRejected)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_contract_recipient_allowed(accounts.charlie, true).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.transfer(accounts.charlie, 70), Err(Error::ExceedsMaxTx));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_max_tx_amount(None).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer(accounts.charlie, 70),
+                Err(Error::InsufficientBalance { available: 50, requested: 70 })
+            );
+            assert_eq!(_erc20.transfer(accounts.charlie, 40), Err(Error::InsufficientUnlockedBalance));
+            assert!(_erc20.transfer(accounts.charlie, 20).is_ok());
+        }
+        #[ink::test]
+        fn transfer_sponsored_works() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert!(_erc20.transfer(accounts.django, 100).is_ok());
+            // django sponsors bob
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(_erc20.approve(accounts.bob, 15).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_sponsored(accounts.django, accounts.charlie, 40, 5).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 60);
+            assert_eq!(_erc20.balance_of(accounts.charlie), 40);
+            assert_eq!(_erc20.balance_of(accounts.django), 100 - 5);
+            // the fee is held for the owner
+            assert_eq!(_erc20.balance_of(contract), 5);
+            assert_eq!(_erc20.pending_of(accounts.alice), 5);
+            assert_eq!(_erc20.allowance(accounts.django, accounts.bob), 10);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::SponsoredTransfer(SponsoredTransfer { sponsor, from, to, value }) => {
+                    assert_eq!(sponsor, accounts.django, "sponsor error");
+                    assert_eq!(from, accounts.bob, "sponsored from error");
+                    assert_eq!(to, accounts.charlie, "sponsored to error");
+                    assert_eq!(value, 40, "sponsored value error");
+                },
+                _ => panic!("match invalid event")
+            }
+
+            assert_eq!(
+                _erc20.transfer_sponsored(accounts.django, accounts.charlie, 40, 11),
+                Err(Error::InsufficientAllowance { available: 10, requested: 11 })
+            );
+            assert_eq!(_erc20.balance_of(accounts.bob), 60);
+
+            // nobody can claim a sponsorship without paying a fee
+            let events_before = ink::env::test::recorded_events().count();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                _erc20.transfer_sponsored(accounts.django, accounts.bob, 10, 0),
+                Err(Error::ZeroAmount)
+            );
+            assert_eq!(
+                _erc20.transfer_sponsored(accounts.django, accounts.bob, 10, 1),
+                Err(Error::InsufficientAllowance { available: 0, requested: 1 })
+            );
+            assert_eq!(_erc20.balance_of(accounts.charlie), 40);
+            assert_eq!(ink::env::test::recorded_events().count(), events_before);
+
+            // the owner collects the fees
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(_erc20.claim_pending(), Ok(5));
+            assert_eq!(_erc20.balance_of(accounts.alice), 1000 - 200 + 5);
+            assert_eq!(_erc20.balance_of(contract), 0);
+        }
+        #[ink::test]
+        fn insufficient_errors_report_amounts() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 30).is_ok());
+            assert!(_erc20.approve(accounts.charlie, 20).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer(accounts.django, 45),
+                Err(Error::InsufficientBalance { available: 30, requested: 45 })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.django, 25),
+                Err(Error::InsufficientAllowance { available: 20, requested: 25 })
+            );
+        }
+        #[ink::test]
+        fn total_burned_accumulates() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert_eq!(_erc20.total_burned(), 0);
+
+            assert!(_erc20.burn_and_revoke(100, accounts.bob).is_ok());
+            assert!(_erc20.mint(accounts.bob, 50).is_ok());
+            assert!(_erc20.burn_and_revoke(30, accounts.bob).is_ok());
+            assert_eq!(_erc20.total_burned(), 130);
+            assert_eq!(_erc20.total_supply(), 1000 + 50 - 130);
+
+            // failed burns don't count
+            assert!(_erc20.burn_and_revoke(10000, accounts.bob).is_err());
+            assert_eq!(_erc20.total_burned(), 130);
+        }
+        #[ink::test]
+        fn circulating_supply_excludes_marked_accounts() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let treasury = accounts.django;
+            assert!(_erc20.transfer(treasury, 100).is_ok());
+            assert_eq!(_erc20.circulating_supply(), 1000);
+
+            assert!(_erc20.mark_non_circulating(treasury, true).is_ok());
+            assert!(_erc20.mark_non_circulating(treasury, true).is_ok());
+            assert!(_erc20.is_non_circulating(treasury));
+            assert_eq!(_erc20.circulating_supply(), 900);
+
+            assert!(_erc20.transfer(treasury, 200).is_ok());
+            assert!(_erc20.mint(treasury, 50).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(treasury);
+            assert!(_erc20.transfer(accounts.bob, 30).is_ok());
+            assert!(_erc20.burn_and_revoke(20, accounts.bob).is_ok());
+            assert_eq!(_erc20.balance_of(treasury), 300);
+            assert_eq!(
+                _erc20.circulating_supply(),
+                _erc20.total_supply() - _erc20.balance_of(treasury)
+            );
+            assert_eq!(_erc20.mark_non_circulating(treasury, false), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.mark_non_circulating(treasury, false).is_ok());
+            assert_eq!(_erc20.circulating_supply(), _erc20.total_supply());
+        }
+        #[ink::test]
+        fn allowance_grace_absorbs_dust() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 99).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 100),
+                Err(Error::InsufficientAllowance { available: 99, requested: 100 })
+            );
+            assert_eq!(_erc20.set_allowance_grace(1), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_allowance_grace(1).is_ok());
+            assert_eq!(_erc20.allowance_grace(), 1);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 101),
+                Err(Error::InsufficientAllowance { available: 99, requested: 101 })
+            );
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.charlie), 100);
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 0);
+            // the zeroed allowance doesn't grant the grace again
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 1),
+                Err(Error::InsufficientAllowance { available: 0, requested: 1 })
+            );
+        }
+        #[ink::test]
+        fn allowance_grace_needs_an_allowance() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_allowance_grace(5).is_ok());
+            assert!(_erc20.approve(accounts.charlie, 10).is_ok());
+            assert!(_erc20.revoke_approval(accounts.charlie).is_ok());
+            assert!(_erc20.approve_at(accounts.django, 10, 5000).is_ok());
+
+            // never approved
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.eve, 5),
+                Err(Error::InsufficientAllowance { available: 0, requested: 5 })
+            );
+            // revoked
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 5),
+                Err(Error::InsufficientAllowance { available: 0, requested: 5 })
+            );
+            // not yet effective
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.django, 5),
+                Err(Error::InsufficientAllowance { available: 0, requested: 5 })
+            );
+            assert_eq!(_erc20.balance_of(accounts.alice), 1000);
+        }
+        #[ink::test]
+        fn claim_pending_pays_out_once() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert!(_erc20.transfer(accounts.django, 100).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert!(_erc20.approve(accounts.bob, 50).is_ok());
+
+            // sponsor fees accumulate for the owner
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_sponsored(accounts.django, accounts.charlie, 10, 30).is_ok());
+            assert!(_erc20.transfer_sponsored(accounts.django, accounts.charlie, 10, 20).is_ok());
+            assert_eq!(_erc20.pending_of(accounts.alice), 50);
+            assert_eq!(_erc20.balance_of(contract), 50);
+            assert_eq!(_erc20.claim_pending(), Ok(0));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(_erc20.claim_pending(), Ok(50));
+            assert_eq!(_erc20.balance_of(accounts.alice), 800 + 50);
+            assert_eq!(_erc20.balance_of(contract), 0);
+            assert_eq!(_erc20.pending_of(accounts.alice), 0);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::Transfer(Transfer { from, to, value }) => {
+                    assert_eq!(from, Some(contract), "claim from error");
+                    assert_eq!(to, Some(accounts.alice), "claim to error");
+                    assert_eq!(value, 50, "claim value error");
+                },
+                _ => panic!("match invalid event")
+            }
+
+            assert_eq!(_erc20.claim_pending(), Ok(0));
+            assert_eq!(_erc20.balance_of(accounts.alice), 800 + 50);
+            assert_eq!(ink::env::test::recorded_events().count(), emitted_events.len());
+        }
+        #[ink::test]
+        fn new_with_allocations_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let _erc20 = Erc20::new_with_allocations(vec![
+                (accounts.alice, 500),
+                (accounts.bob, 300),
+                (accounts.charlie, 150),
+                (accounts.bob, 50),
+            ]);
+            assert_eq!(_erc20.balance_of(accounts.alice), 500);
+            assert_eq!(_erc20.balance_of(accounts.bob), 350);
+            assert_eq!(_erc20.balance_of(accounts.charlie), 150);
+            assert_eq!(_erc20.total_supply(), 1000);
+            assert_eq!(_erc20.owner(), accounts.alice);
+            assert_eq!(
+                _erc20.recent_transfers(),
+                vec![
+                    (None, Some(accounts.alice), 500, 0),
+                    (None, Some(accounts.bob), 350, 0),
+                    (None, Some(accounts.charlie), 150, 0),
+                ]
+            );
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            let expected = [(accounts.alice, 500), (accounts.bob, 350), (accounts.charlie, 150)];
+            for (event, (account, amount)) in emitted_events.iter().zip(expected) {
+                let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+                match decoded {
+                    Event::Transfer(Transfer { from, to, value }) => {
+                        assert_eq!(from, None, "genesis from error");
+                        assert_eq!(to, Some(account), "genesis to error");
+                        assert_eq!(value, amount, "genesis value error");
+                    },
+                    _ => panic!("match invalid event")
+                }
+            }
+        }
+        #[ink::test]
+        fn contract_recipients_can_be_rejected() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let dex = accounts.django;
+            ink::env::test::set_contract::<ink::env::DefaultEnvironment>(contract);
+            ink::env::test::set_contract::<ink::env::DefaultEnvironment>(dex);
+
+            assert!(_erc20.transfer(dex, 10).is_ok());
+
+            assert!(_erc20.set_reject_contract_recipients(true).is_ok());
+            assert!(_erc20.reject_contract_recipients());
+            assert_eq!(_erc20.transfer(dex, 10), Err(Error::ContractRecipientRejected));
+            assert!(_erc20.transfer(accounts.bob, 10).is_ok());
+            // custody in this contract keeps working
+            assert!(_erc20.distribute_dividends(10).is_ok());
+            assert_eq!(_erc20.balance_of(contract), 10);
+
+            assert!(_erc20.set_contract_recipient_allowed(dex, true).is_ok());
+            assert!(_erc20.is_contract_recipient_allowed(dex));
+            assert!(_erc20.transfer(dex, 10).is_ok());
+            assert_eq!(_erc20.balance_of(dex), 20);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.set_reject_contract_recipients(false), Err(Error::NotOwner));
+        }
+        #[cfg(feature = "debug")]
+        #[ink::test]
+        fn raw_balance_cell_distinguishes_unset() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 10).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer(accounts.alice, 10).is_ok());
+
+            assert_eq!(_erc20.raw_balance_cell(accounts.charlie), None);
+            assert_eq!(_erc20.raw_balance_cell(accounts.bob), Some(0));
+            assert_eq!(_erc20.balance_of(accounts.charlie), _erc20.balance_of(accounts.bob));
+        }
+        #[ink::test]
+        fn recent_transfers_keeps_last_window() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            // the genesis mint is recorded
+            assert_eq!(_erc20.recent_transfers(), vec![(None, Some(accounts.alice), 1000, 0)]);
+
+            assert!(_erc20.mint(accounts.bob, 7).is_ok());
+            assert_eq!(
+                _erc20.recent_transfers(),
+                vec![(None, Some(accounts.alice), 1000, 0), (None, Some(accounts.bob), 7, 0)]
+            );
+
+            for value in 1..=HISTORY_LEN as Balance {
+                ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+                assert!(_erc20.transfer(accounts.charlie, value).is_ok());
+            }
+            let history = _erc20.recent_transfers();
+            assert_eq!(history.len(), HISTORY_LEN as usize);
+            assert_eq!(history[0], (Some(accounts.alice), Some(accounts.charlie), 1, 1));
+
+            // the next mint overwrites the oldest transfer
+            assert!(_erc20.mint(accounts.bob, 5).is_ok());
+            let history = _erc20.recent_transfers();
+            assert_eq!(history.len(), HISTORY_LEN as usize);
+            for (record, value) in history.iter().zip(2..=HISTORY_LEN) {
+                assert_eq!(
+                    *record,
+                    (Some(accounts.alice), Some(accounts.charlie), value as Balance, value)
+                );
+            }
+            assert_eq!(history.last(), Some(&(None, Some(accounts.bob), 5, HISTORY_LEN)));
+
+            // zero-value and self transfers emit `Transfer`, so they are recorded too
+            assert!(_erc20.transfer(accounts.charlie, 0).is_ok());
+            assert!(_erc20.transfer(accounts.alice, 3).is_ok());
+            let history = _erc20.recent_transfers();
+            assert_eq!(
+                history[history.len() - 2..],
+                [
+                    (Some(accounts.alice), Some(accounts.charlie), 0, HISTORY_LEN),
+                    (Some(accounts.alice), Some(accounts.alice), 3, HISTORY_LEN),
+                ]
+            );
+        }
+        #[ink::test]
+        fn contract_spenders_only_rejects_accounts() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let router = accounts.django;
+            ink::env::test::set_contract::<ink::env::DefaultEnvironment>(router);
+            assert!(_erc20.approve(router, 100).is_ok());
+            assert!(_erc20.approve(accounts.bob, 100).is_ok());
+            assert!(_erc20.set_contract_spenders_only(true).is_ok());
+            assert!(_erc20.contract_spenders_only());
+
+            // contracts must also be allowlisted
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(router);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 10),
+                Err(Error::SpenderNotContract)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_spender_allowed(router, true).is_ok());
+            assert!(_erc20.set_spender_allowed(accounts.bob, true).is_ok());
+            assert!(_erc20.is_spender_allowed(router));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(router);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 10).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.charlie), 10);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 10),
+                Err(Error::SpenderNotContract)
+            );
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+        #[ink::test]
+        fn supports_interface_works() {
+            let _erc20 = Erc20::new(1000);
+            let transfer = ink::selector_bytes!("transfer");
+            assert_ne!(ERC20_INTERFACE_ID, transfer);
+            assert!(_erc20.supports_interface(ERC20_INTERFACE_ID));
+            assert!(_erc20.supports_interface(ERC20_METADATA_INTERFACE_ID));
+            assert!(_erc20.supports_interface(INTERFACE_DETECTION_ID));
+            assert!(!_erc20.supports_interface(transfer));
+            assert!(!_erc20.supports_interface([0xff; 4]));
+        }
+        #[ink::test]
+        fn set_decimals_until_locked() {
+            let mut _erc20 = Erc20::new_with_metadata(1000, None, None, 6);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_decimals(18).is_ok());
+            assert_eq!(_erc20.token_decimals(), 18);
+            assert!(!_erc20.decimals_locked());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.set_decimals(8), Err(Error::NotOwner));
+            assert_eq!(_erc20.lock_decimals(), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.lock_decimals().is_ok());
+            assert!(_erc20.decimals_locked());
+            assert_eq!(_erc20.set_decimals(8), Err(Error::DecimalsLocked));
+            assert_eq!(_erc20.token_decimals(), 18);
+        }
+        #[ink::test]
+        fn transfer_weighted_splits_by_shares() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let split = vec![(accounts.bob, 6000), (accounts.charlie, 4000)];
+            assert!(_erc20.transfer_weighted(500, split).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 300);
+            assert_eq!(_erc20.balance_of(accounts.charlie), 200);
+
+            // 101 * 60% = 60.6 and 101 * 40% = 40.4, the remainder goes to bob
+            let split = vec![(accounts.bob, 6000), (accounts.charlie, 4000)];
+            assert!(_erc20.transfer_weighted(101, split).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 300 + 61);
+            assert_eq!(_erc20.balance_of(accounts.charlie), 200 + 40);
+            assert_eq!(_erc20.balance_of(accounts.alice), 1000 - 500 - 101);
+
+            let split = vec![(accounts.bob, 6000), (accounts.charlie, 3000)];
+            assert_eq!(_erc20.transfer_weighted(100, split), Err(Error::InvalidShares));
+            assert_eq!(_erc20.transfer_weighted(100, Vec::new()), Err(Error::InvalidShares));
+            assert_eq!(_erc20.balance_of(accounts.alice), 399);
+        }
+        #[ink::test]
+        fn transfer_weighted_honors_summary_only() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let split = vec![(accounts.bob, 6000), (accounts.charlie, 4000)];
+
+            // one per recipient and the summary
+            let events_before = ink::env::test::recorded_events().count();
+            assert!(_erc20.transfer_weighted(100, split.clone()).is_ok());
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 2 + 1);
+
+            assert!(_erc20.set_batch_summary_only(true).is_ok());
+            let events_before = ink::env::test::recorded_events().count();
+            assert!(_erc20.transfer_weighted(100, split).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 120);
+            assert_eq!(_erc20.balance_of(accounts.charlie), 80);
+
+            // only the summary
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), events_before + 1);
+            let event = &emitted_events[events_before];
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::BatchTransfer(BatchTransfer { from, count, total }) => {
+                    assert_eq!(from, Some(accounts.alice), "batch from error");
+                    assert_eq!(count, 2, "batch count error");
+                    assert_eq!(total, 100, "batch total error");
+                },
+                _ => panic!("match invalid event")
+            }
+        }
+        #[ink::test]
+        fn approve_at_delays_spending() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve_at(accounts.bob, 100, 5000).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 100);
+            assert_eq!(_erc20.allowance_effective_at(accounts.alice, accounts.bob), 5000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(4999);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 10),
+                Err(Error::InsufficientAllowance { available: 0, requested: 10 })
+            );
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 10).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 90);
+
+            // a plain approve takes effect immediately
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.approve_at(accounts.bob, 50, 9000).is_ok());
+            assert!(_erc20.approve(accounts.bob, 50).is_ok());
+            assert_eq!(_erc20.allowance_effective_at(accounts.alice, accounts.bob), 0);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 50).is_ok());
+        }
+        #[ink::test]
+        fn admin_dashboard_reflects_configuration() {
+            set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.django, 100).is_ok());
+            assert!(_erc20.mark_non_circulating(accounts.django, true).is_ok());
+            assert!(_erc20.burn_and_revoke(50, accounts.bob).is_ok());
+            assert!(_erc20.set_max_tx_amount(Some(500)).is_ok());
+            assert!(_erc20.set_same_block_guard(true).is_ok());
+            assert!(_erc20.set_staking_reward_rate(3).is_ok());
+            assert!(_erc20.fund_rewards(200).is_ok());
+
+            assert_eq!(
+                _erc20.admin_dashboard(),
+                AdminInfo {
+                    owner: accounts.alice,
+                    total_supply: 950,
+                    circulating_supply: 850,
+                    total_burned: 50,
+                    max_tx_amount: Some(500),
+                    same_block_guard: true,
+                    staking_reward_rate: 3,
+                    reward_pool: 200,
+                }
+            );
+        }
+        #[ink::test]
+        fn freeze_batch_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let swept = vec![accounts.bob, accounts.charlie, accounts.django];
+
+            assert!(_erc20.freeze_batch(swept.clone()).is_ok());
+            assert!(swept.iter().all(|account| _erc20.is_frozen(*account)));
+            let frozen_events = ink::env::test::recorded_events()
+                .filter(|event| {
+                    matches!(
+                        <Event as scale::Decode>::decode(&mut &event.data[..]),
+                        Ok(Event::AccountFrozen(_))
+                    )
+                })
+                .count();
+            assert_eq!(frozen_events, 3);
+
+            assert!(_erc20.unfreeze_batch(vec![accounts.bob, accounts.django]).is_ok());
+            assert!(!_erc20.is_frozen(accounts.bob));
+            assert!(_erc20.is_frozen(accounts.charlie));
+            assert!(!_erc20.is_frozen(accounts.django));
+
+            let too_many = vec![accounts.eve; MAX_FREEZE_BATCH + 1];
+            assert_eq!(_erc20.freeze_batch(too_many), Err(Error::BatchTooLarge));
+            assert!(!_erc20.is_frozen(accounts.eve));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.unfreeze_batch(vec![accounts.charlie]), Err(Error::NotOwner));
+        }
+        #[ink::test]
+        fn auto_top_up_refills_allowance() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 10).is_ok());
+            assert!(_erc20.set_auto_top_up(accounts.bob, Some(100)).is_ok());
+            assert_eq!(_erc20.auto_top_up(accounts.alice, accounts.bob), Some(100));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 60).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(_erc20.auto_top_up(accounts.alice, accounts.bob), Some(50));
+
+            // the budget is spent across calls
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 30).is_ok());
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 21),
+                Err(Error::InsufficientAllowance { available: 20, requested: 21 })
+            );
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 20).is_ok());
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 1),
+                Err(Error::InsufficientAllowance { available: 0, requested: 1 })
+            );
+            assert_eq!(_erc20.balance_of(accounts.charlie), 10 + 100);
+
+            // the refill is also limited by alice's balance
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_auto_top_up(accounts.bob, Some(500)).is_ok());
+            assert!(_erc20.transfer(accounts.django, 850).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 41),
+                Err(Error::InsufficientAllowance { available: 40, requested: 41 })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_auto_top_up(accounts.bob, None).is_ok());
+            assert_eq!(_erc20.auto_top_up(accounts.alice, accounts.bob), None);
+        }
+        #[ink::test]
+        fn revoke_removes_auto_top_up() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 10).is_ok());
+            assert!(_erc20.set_auto_top_up(accounts.bob, Some(100)).is_ok());
+            assert!(_erc20.revoke_approval(accounts.bob).is_ok());
+            assert_eq!(_erc20.auto_top_up(accounts.alice, accounts.bob), None);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 100),
+                Err(Error::InsufficientAllowance { available: 0, requested: 100 })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.set_auto_top_up(accounts.bob, Some(100)).is_ok());
+            assert!(_erc20.approve(accounts.bob, 0).is_ok());
+            assert_eq!(_erc20.auto_top_up(accounts.alice, accounts.bob), None);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                _erc20.transfer_from(accounts.alice, accounts.charlie, 1),
+                Err(Error::InsufficientAllowance { available: 0, requested: 1 })
+            );
+            assert_eq!(_erc20.balance_of(accounts.alice), 1000);
+        }
+        #[ink::test]
+        fn finalize_supply_disables_mint_and_burn() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.mint(accounts.bob, 100).is_ok());
+            assert!(!_erc20.supply_fixed());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.finalize_supply(), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.finalize_supply().is_ok());
+            assert!(_erc20.supply_fixed());
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::SupplyFinalized(SupplyFinalized { total_supply }) => {
+                    assert_eq!(total_supply, 1100, "finalized supply error");
+                },
+                _ => panic!("match invalid event")
+            }
+
+            assert_eq!(_erc20.mint(accounts.bob, 1), Err(Error::SupplyFixed));
+            assert_eq!(_erc20.mint_batch(vec![(accounts.bob, 1)]), Err(Error::SupplyFixed));
+            assert_eq!(_erc20.burn_and_revoke(1, accounts.bob), Err(Error::SupplyFixed));
+            assert_eq!(_erc20.finalize_supply(), Err(Error::SupplyFixed));
+            assert_eq!(_erc20.total_supply(), 1100);
+            assert!(_erc20.transfer(accounts.bob, 10).is_ok());
+        }
+        #[ink::test]
+        fn wrapped_tokens_redeemable_after_finalize() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert!(_erc20.deposit().is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.finalize_supply().is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.deposit(), Err(Error::SupplyFixed));
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert!(_erc20.withdraw(100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 0);
+            assert_eq!(_erc20.wrapped_supply(), 0);
+            // unwrapping still shrinks the fixed supply
+            assert_eq!(_erc20.total_supply(), 1000);
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contract),
+                Ok(0)
+            );
+        }
+        #[ink::test]
+        fn balances_of_works() {
+            let mut _erc20 = Erc20::new(10000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 12).is_ok());
+
+            let balances = _erc20.balances_of(vec![accounts.alice, accounts.bob, accounts.django]);
+            assert_eq!(balances, vec![10000 - 12, 12, 0]);
+        }
+        #[ink::test]
+        fn check_transfer_allowed_applies_to_transfer_from() {
+            let mut _erc20 = Erc20::new(100);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 1000).is_ok());
+
+            assert_eq!(_erc20.check_transfer_allowed(&accounts.alice, &accounts.charlie, 100), Ok(()));
+            assert_eq!(
+                _erc20.check_transfer_allowed(&accounts.alice, &accounts.charlie, 101),
+                Err(Error::InsufficientBalance { available: 100, requested: 101 })
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let res = _erc20.transfer_from(accounts.alice, accounts.charlie, 101);
+            assert_eq!(res, Err(Error::InsufficientBalance { available: 100, requested: 101 }));
+            assert_eq!(_erc20.balance_of(accounts.alice), 100);
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 1000);
+        }
+        #[ink::test]
+        fn transfer_with_memo_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(_erc20.transfer_with_memo(accounts.bob, 10, b"deposit-42".to_vec()).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 10);
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::TransferWithMemo(TransferWithMemo { from, to, value, memo }) => {
+                    assert_eq!(from, accounts.alice, "memo from error");
+                    assert_eq!(to, accounts.bob, "memo to error");
+                    assert_eq!(value, 10, "memo value error");
+                    assert_eq!(memo, b"deposit-42".to_vec(), "memo error");
+                },
+                _ => panic!("match invalid event")
+            }
+        }
+        #[ink::test]
+        fn transfer_with_long_memo_fails() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
 
-        use ink::primitives::{
-            Clear,
-            Hash,
-        };
+            let res = _erc20.transfer_with_memo(accounts.bob, 10, vec![0u8; MAX_MEMO_LEN + 1]);
+            assert_eq!(res, Err(Error::MemoTooLong));
+            assert_eq!(_erc20.balance_of(accounts.bob), 0);
+        }
+        #[ink::test]
+        fn transfer_from_emits_delegated_transfer() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 100).is_ok());
 
-        type Event = <Erc20 as ::ink::reflect::ContractEventBase>::Type;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 30).is_ok());
 
-        /// The default constructor does its job.
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
+            let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::DelegatedTransfer(DelegatedTransfer { spender, from, to, value }) => {
+                    assert_eq!(spender, accounts.bob, "delegated spender error");
+                    assert_eq!(from, accounts.alice, "delegated from error");
+                    assert_eq!(to, accounts.charlie, "delegated to error");
+                    assert_eq!(value, 30, "delegated value error");
+                },
+                _ => panic!("match invalid event")
+            }
+        }
         #[ink::test]
-        fn constructor_works() {
-            // Constructor works.
-            let _erc20 = Erc20::new(10000);
+        fn transfer_from_emits_remaining_approval() {
+            let mut _erc20 = Erc20::new(1000);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            
-            assert_eq!(_erc20.total_supply(), 10000);
-            assert_eq!(_erc20.balance_of(accounts.alice), 10000);
+            assert!(_erc20.approve(accounts.bob, 100).is_ok());
 
-            let eniteed_events = ink::env::test::recorded_events().collect::<Vec<_>>();
-            let event = &eniteed_events[0];
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.charlie, 30).is_ok());
+
+            let decoded = ink::env::test::recorded_events()
+                .skip(2)
+                .map(|event| <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error"))
+                .collect::<Vec<_>>();
+            match &decoded[..] {
+                [Event::Transfer(transfer), Event::Approval(approval), Event::DelegatedTransfer(_)] => {
+                    assert_eq!(transfer.from, Some(accounts.alice), "transfer from error");
+                    assert_eq!(transfer.to, Some(accounts.charlie), "transfer to error");
+                    assert_eq!(transfer.value, 30, "transfer value error");
+                    assert_eq!(approval.owner, accounts.alice, "approval owner error");
+                    assert_eq!(approval.spender, accounts.bob, "approval spender error");
+                    assert_eq!(approval.value, 70, "approval value error");
+                },
+                _ => panic!("match invalid event")
+            }
+        }
+        #[ink::test]
+        fn minter_roles_work() {
+            let mut _erc20 = Erc20::new(100);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.is_minter(accounts.alice));
+            assert!(!_erc20.is_minter(accounts.bob));
+
+            assert!(_erc20.grant_minter(accounts.bob).is_ok());
+            assert!(_erc20.is_minter(accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.mint(accounts.charlie, 50).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.charlie), 50);
+            assert_eq!(_erc20.total_supply(), 150);
+            assert_eq!(_erc20.grant_minter(accounts.bob), Err(Error::NotOwner));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.revoke_minter(accounts.bob).is_ok());
+            assert!(!_erc20.is_minter(accounts.bob));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.mint(accounts.charlie, 50), Err(Error::NotMinter));
+            assert_eq!(_erc20.total_supply(), 150);
+        }
+        #[ink::test]
+        fn mint_overflow_fails() {
+            let mut _erc20 = Erc20::new(Balance::MAX);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert_eq!(_erc20.mint(accounts.bob, 1), Err(Error::Overflow));
+            assert_eq!(_erc20.total_supply(), Balance::MAX);
+            assert_eq!(_erc20.balance_of(accounts.bob), 0);
+        }
+        #[ink::test]
+        fn safe_approve_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            assert!(_erc20.safe_approve(accounts.bob, 0, 100).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 100);
+        }
+        #[ink::test]
+        fn safe_approve_mismatch_fails() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 100).is_ok());
 
+            // the allowance is partially spent before the update lands
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer_from(accounts.alice, accounts.bob, 50).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                _erc20.safe_approve(accounts.bob, 100, 200),
+                Err(Error::AllowanceMismatch)
+            );
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 50);
+        }
+        #[ink::test]
+        fn revoke_approval_clears_entry() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 100).is_ok());
+            assert!(_erc20.allowances.contains((accounts.alice, accounts.bob)));
+
+            assert!(_erc20.revoke_approval(accounts.bob).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert!(!_erc20.allowances.contains((accounts.alice, accounts.bob)));
+
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            let event = emitted_events.last().expect("no event emitted");
             let decoded = <Event as scale::Decode>::decode(&mut &event.data[..]).expect("decode error");
+            match decoded {
+                Event::ApprovalRevoked(ApprovalRevoked { owner, spender }) => {
+                    assert_eq!(owner, accounts.alice, "revoke owner error");
+                    assert_eq!(spender, accounts.bob, "revoke spender error");
+                },
+                _ => panic!("match invalid event")
+            }
+        }
+        #[ink::test]
+        fn increase_allowance_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 100).is_ok());
+
+            assert!(_erc20.increase_allowance(accounts.bob, 50).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 150);
+        }
+        #[ink::test]
+        fn increase_allowance_overflow_fails() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, Balance::MAX - 1).is_ok());
+
+            assert_eq!(_erc20.increase_allowance(accounts.bob, 2), Err(Error::Overflow));
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), Balance::MAX - 1);
+        }
+        #[ink::test]
+        fn mint_batch_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let entries = vec![(accounts.bob, 10), (accounts.charlie, 20), (accounts.django, 30)];
+            assert!(_erc20.mint_batch(entries).is_ok());
+            assert_eq!(_erc20.total_supply(), 1000 + 60);
+            assert_eq!(_erc20.balances_of(vec![accounts.bob, accounts.charlie, accounts.django]), vec![10, 20, 30]);
+            // the constructor's mint, one per entry and the summary
+            assert_eq!(ink::env::test::recorded_events().count(), 1 + 3 + 1);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.mint_batch(vec![(accounts.bob, 1)]), Err(Error::NotMinter));
+        }
+        #[ink::test]
+        fn mint_batch_emits_summary() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_batch_summary_only(true).is_ok());
 
+            let entries = vec![(accounts.bob, 10), (accounts.charlie, 20), (accounts.django, 30)];
+            assert!(_erc20.mint_batch(entries).is_ok());
+            assert_eq!(_erc20.total_supply(), 1000 + 60);
+
+            // only the constructor's mint and the summary
+            let emitted_events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            let decoded = <Event as scale::Decode>::decode(&mut &emitted_events[1].data[..]).expect("decode error");
             match decoded {
-                Event::Transfer(Transfer { from, to, value }) => {
-                    assert!(from.is_none(),"mint from error");
-                    assert_eq!(to, Some(accounts.alice),"mint to error");
-                    assert_eq!(value, 10000,"mint value error");
+                Event::BatchTransfer(BatchTransfer { from, count, total }) => {
+                    assert!(from.is_none(), "batch from error");
+                    assert_eq!(count, 3, "batch count error");
+                    assert_eq!(total, 60, "batch total error");
                 },
                 _ => panic!("match invalid event")
             }
         }
         #[ink::test]
-        fn transfer_should_work() {
+        fn mint_batch_overflow_is_atomic() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+
+            let entries = vec![(accounts.bob, 10), (accounts.charlie, Balance::MAX - 1000)];
+            assert_eq!(_erc20.mint_batch(entries), Err(Error::Overflow));
+            assert_eq!(_erc20.total_supply(), 1000);
+            assert_eq!(_erc20.balance_of(accounts.bob), 0);
+            assert_eq!(_erc20.balance_of(accounts.charlie), 0);
+        }
+        #[ink::test]
+        fn adjust_allowances_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.django, 100).is_ok());
+
+            let adjustments = vec![(accounts.bob, 10), (accounts.charlie, 20), (accounts.django, -40)];
+            assert!(_erc20.adjust_allowances(adjustments).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.charlie), 20);
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.django), 60);
+        }
+        #[ink::test]
+        fn adjust_allowances_is_atomic() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.django, 100).is_ok());
+
+            let adjustments = vec![(accounts.bob, 10), (accounts.django, -60), (accounts.django, -60)];
+            assert_eq!(
+                _erc20.adjust_allowances(adjustments),
+                Err(Error::InsufficientAllowance { available: 40, requested: 60 })
+            );
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 0);
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.django), 100);
+        }
+        #[ink::test]
+        fn deposit_and_withdraw_work() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(0);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(accounts.bob, 1000);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(300);
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 300);
+            assert!(_erc20.deposit().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 300);
+            assert_eq!(_erc20.total_supply(), 300);
+
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+            assert!(_erc20.withdraw(100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 200);
+            assert_eq!(_erc20.total_supply(), 200);
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(accounts.bob),
+                Ok(1100)
+            );
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contract),
+                Ok(200)
+            );
+            assert_eq!(_erc20.withdraw(201), Err(Error::InsufficientBacking));
+            assert_eq!(_erc20.wrapped_balance_of(accounts.bob), 200);
+            assert_eq!(_erc20.wrapped_supply(), 200);
+        }
+        #[ink::test]
+        fn withdraw_requires_deposited_backing() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(0);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert!(_erc20.deposit().is_ok());
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            // minted tokens aren't backed
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.mint(accounts.alice, 1_000_000).is_ok());
+            assert_eq!(_erc20.withdraw(100), Err(Error::InsufficientBacking));
+
+            // neither are deposited tokens received from others
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer(accounts.charlie, 40).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert_eq!(_erc20.withdraw(40), Err(Error::InsufficientBacking));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.withdraw(60).is_ok());
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contract),
+                Ok(40)
+            );
+            assert_eq!(
+                _erc20.withdraw(40),
+                Err(Error::InsufficientBalance { available: 0, requested: 40 })
+            );
+        }
+        #[ink::test]
+        fn failed_native_transfer_keeps_tokens() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(0);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(300);
+            assert!(_erc20.deposit().is_ok());
+
+            // the contract account holds no native currency to send; on-chain the
+            // error reverts the burn, which the off-chain environment doesn't model
+            assert!(ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contract).is_err());
+            assert_eq!(_erc20.withdraw(100), Err(Error::NativeTransferFailed));
+            assert_eq!(_erc20.balance_of(accounts.alice), 200);
+        }
+        #[ink::test]
+        fn frozen_account_cannot_burn_or_withdraw() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert!(_erc20.deposit().is_ok());
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.freeze(accounts.bob).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.withdraw(100), Err(Error::AccountFrozen));
+            assert_eq!(_erc20.burn_and_revoke(10, accounts.charlie), Err(Error::AccountFrozen));
+            assert_eq!(_erc20.balance_of(accounts.bob), 100);
+            assert_eq!(
+                ink::env::test::get_account_balance::<ink::env::DefaultEnvironment>(contract),
+                Ok(100)
+            );
+        }
+        #[ink::test]
+        fn custody_account_cannot_be_frozen() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 400).is_ok());
+            // alice 400, bob 400: 200 shared over 800 tokens
+            assert!(_erc20.distribute_dividends(200).is_ok());
+
+            assert_eq!(_erc20.freeze(contract), Err(Error::CustodyAccount));
+            assert_eq!(
+                _erc20.freeze_batch(vec![accounts.charlie, contract]),
+                Err(Error::CustodyAccount)
+            );
+            assert!(!_erc20.is_frozen(contract));
+            assert!(!_erc20.is_frozen(accounts.charlie));
+
+            // payouts from custody keep working
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.withdraw_dividend().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 400 + 100);
+        }
+        #[ink::test]
+        fn custody_account_cannot_be_locked() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 400).is_ok());
+            assert!(_erc20.distribute_dividends(200).is_ok());
+
+            assert_eq!(_erc20.lock_until(contract, u64::MAX), Err(Error::CustodyAccount));
+            assert_eq!(_erc20.lock_balance(contract, 200), Err(Error::CustodyAccount));
+            assert_eq!(_erc20.locked_until(contract), 0);
+            assert_eq!(_erc20.locked_balance(contract), 0);
+
+            // payouts from custody keep working
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.withdraw_dividend().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 400 + 100);
+        }
+        #[ink::test]
+        fn locked_balance_cannot_be_burned_or_withdrawn() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert!(_erc20.deposit().is_ok());
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.lock_balance(accounts.bob, 70).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.withdraw(31), Err(Error::InsufficientUnlockedBalance));
+            assert_eq!(
+                _erc20.burn_and_revoke(31, accounts.charlie),
+                Err(Error::InsufficientUnlockedBalance)
+            );
+            assert!(_erc20.withdraw(30).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 70);
+        }
+        #[ink::test]
+        fn time_locked_account_cannot_burn_or_withdraw() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(contract, 100);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(100);
+            assert!(_erc20.deposit().is_ok());
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(0);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.lock_until(accounts.bob, 1000).is_ok());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(999);
+            assert_eq!(_erc20.withdraw(100), Err(Error::TokensLocked));
+            assert_eq!(_erc20.burn_and_revoke(10, accounts.charlie), Err(Error::TokensLocked));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert!(_erc20.withdraw(100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 0);
+        }
+        #[ink::test]
+        fn burn_and_revoke_works() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.approve(accounts.bob, 300).is_ok());
+            assert!(_erc20.approve(accounts.charlie, 50).is_ok());
+
+            assert!(_erc20.burn_and_revoke(100, accounts.bob).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 900);
+            assert_eq!(_erc20.total_supply(), 900);
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 200);
+
+            // the allowance is floored at zero
+            assert!(_erc20.burn_and_revoke(100, accounts.charlie).is_ok());
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.charlie), 0);
+            assert_eq!(_erc20.total_supply(), 800);
+
+            assert_eq!(
+                _erc20.burn_and_revoke(801, accounts.bob),
+                Err(Error::InsufficientBalance { available: 800, requested: 801 })
+            );
+            assert_eq!(_erc20.allowance(accounts.alice, accounts.bob), 200);
+        }
+        #[ink::test]
+        fn vesting_releases_linearly() {
+            let contract = set_contract_account();
             let mut _erc20 = Erc20::new(10000);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let res = _erc20.transfer(accounts.bob,12);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
 
-            assert!(res.is_ok());
-            assert_eq!(_erc20.balance_of(accounts.alice),10000-12);
-            assert_eq!(_erc20.balance_of(accounts.bob),12);
-        }   
+            assert!(_erc20.create_vesting(accounts.bob, 1000, 2000, 1000).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 9000);
+            assert_eq!(_erc20.balance_of(contract), 1000);
+            assert_eq!(
+                _erc20.create_vesting(accounts.bob, 1000, 2000, 1000),
+                Err(Error::VestingExists)
+            );
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.vested_amount(accounts.bob), 0);
+            assert_eq!(_erc20.release(), Err(Error::NothingToRelease));
+
+            // 50%
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2500);
+            assert_eq!(_erc20.vested_amount(accounts.bob), 500);
+            assert_eq!(_erc20.releasable(accounts.bob), 500);
+            assert!(_erc20.release().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 500);
+            assert_eq!(_erc20.releasable(accounts.bob), 0);
+            assert_eq!(_erc20.release(), Err(Error::NothingToRelease));
+
+            // 100%
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5000);
+            assert_eq!(_erc20.vested_amount(accounts.bob), 1000);
+            assert_eq!(_erc20.releasable(accounts.bob), 500);
+            assert!(_erc20.release().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 1000);
+            assert_eq!(_erc20.balance_of(contract), 0);
+            assert_eq!(_erc20.release(), Err(Error::NothingToRelease));
+        }
         #[ink::test]
-        fn invalid_transfer_should_work() {
+        fn dividends_follow_transfers() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1100);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 300).is_ok());
+            assert!(_erc20.transfer(accounts.charlie, 100).is_ok());
+
+            // alice 700, bob 300: 100 shared over 1000 tokens
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            assert!(_erc20.distribute_dividends(100).is_ok());
+            assert_eq!(_erc20.balance_of(contract), 100);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.alice), 70);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.bob), 30);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.charlie), 0);
+
+            // transferred tokens neither carry nor lose accrued dividends
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer(accounts.alice, 100).is_ok());
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.alice), 70);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.bob), 30);
+
+            // alice 600, bob 200: 200 shared over 800 tokens
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.distribute_dividends(200).is_ok());
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.alice), 70 + 150);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.bob), 30 + 50);
+
+            assert!(_erc20.withdraw_dividend().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 600 + 220);
+            assert_eq!(_erc20.withdrawable_dividend_of(accounts.alice), 0);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.alice), 220);
+            assert_eq!(_erc20.withdraw_dividend(), Err(Error::NoDividend));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.withdraw_dividend().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 200 + 80);
+            assert_eq!(_erc20.balance_of(contract), 0);
+        }
+        #[ink::test]
+        fn dividends_beyond_supply_bound_are_rejected() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(100_000_000_000_000_000_000_000_000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 30_000_000_000_000_000_000_000_000).is_ok());
+
+            // 70% of the supply shared over the other 30% exceeds the bound
+            assert_eq!(
+                _erc20.distribute_dividends(70_000_000_000_000_000_000_000_000),
+                Err(Error::Overflow)
+            );
+            assert_eq!(_erc20.balance_of(contract), 0);
+
+            assert!(_erc20.distribute_dividends(20_000_000_000_000_000_000_000_000).is_ok());
+            let accrued = _erc20.accumulated_dividend_of(accounts.bob);
+            assert_eq!(accrued, 7_500_000_000_000_000_000_000_000);
+
+            // minting can't push the supply past the bound either
+            assert_eq!(
+                _erc20.mint(accounts.bob, 1_000_000_000_000_000_000_000_000_000),
+                Err(Error::Overflow)
+            );
+            assert_eq!(
+                _erc20.mint_batch(vec![(accounts.bob, 1_000_000_000_000_000_000_000_000_000)]),
+                Err(Error::Overflow)
+            );
+            assert_eq!(_erc20.total_supply(), 100_000_000_000_000_000_000_000_000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.transfer(accounts.charlie, 30_000_000_000_000_000_000_000_000).is_ok());
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.bob), accrued);
+            assert_eq!(_erc20.accumulated_dividend_of(accounts.charlie), 0);
+        }
+        #[ink::test]
+        fn dust_dividends_are_rejected() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(2 * DIVIDEND_MAGNITUDE);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 1).is_ok());
+
+            // 1 token over more than DIVIDEND_MAGNITUDE shares rounds to nothing
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(_erc20.distribute_dividends(1), Err(Error::NothingToDistribute));
+            assert_eq!(_erc20.balance_of(accounts.bob), 1);
+            assert_eq!(_erc20.balance_of(contract), 0);
+        }
+        #[ink::test]
+        fn staking_rewards_accrue_per_share() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 400).is_ok());
+            assert!(_erc20.set_staking_reward_rate(1).is_ok());
+            assert!(_erc20.mint(accounts.alice, 2000).is_ok());
+            assert!(_erc20.fund_rewards(2000).is_ok());
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            assert!(_erc20.stake(100).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.stake(400).is_ok());
+            assert_eq!(_erc20.total_staked(), 500);
+            assert_eq!(_erc20.balance_of(contract), 500 + 2000);
+
+            // 1000 rewards shared 1:4
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+            assert_eq!(_erc20.earned(accounts.alice), 200);
+            assert_eq!(_erc20.earned(accounts.bob), 800);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.claim_staking_rewards().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 500 + 200);
+            assert_eq!(_erc20.reward_pool(), 1800);
+            assert_eq!(_erc20.total_supply(), 3000);
+            assert_eq!(_erc20.claim_staking_rewards(), Err(Error::NoReward));
+            assert_eq!(_erc20.unstake(101), Err(Error::InsufficientStake));
+            assert!(_erc20.unstake(100).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 800);
+            assert_eq!(_erc20.staked_of(accounts.alice), 0);
+
+            // bob earns everything once he is the only staker
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3000);
+            assert_eq!(_erc20.earned(accounts.alice), 0);
+            assert_eq!(_erc20.earned(accounts.bob), 800 + 1000);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.claim_staking_rewards().is_ok());
+            assert!(_erc20.unstake(400).is_ok());
+            assert_eq!(_erc20.balance_of(accounts.bob), 400 + 1800);
+            assert_eq!(_erc20.total_staked(), 0);
+            assert_eq!(_erc20.reward_pool(), 0);
+            assert_eq!(_erc20.balance_of(contract), 0);
+        }
+        #[ink::test]
+        fn auto_compound_restakes_rewards() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert!(_erc20.set_staking_reward_rate(1).is_ok());
+            assert!(_erc20.mint(accounts.alice, 1000).is_ok());
+            assert!(_erc20.fund_rewards(1000).is_ok());
+
+            assert!(_erc20.set_auto_compound(true).is_ok());
+            assert!(_erc20.stake(100).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.stake(100).is_ok());
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.claim_staking_rewards().is_ok());
+            assert_eq!(_erc20.staked_of(accounts.alice), 100 + 500);
+            assert_eq!(_erc20.balance_of(accounts.alice), 800);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.claim_staking_rewards().is_ok());
+            assert_eq!(_erc20.staked_of(accounts.bob), 100);
+            assert_eq!(_erc20.balance_of(accounts.bob), 500);
+
+            assert_eq!(_erc20.total_staked(), 700);
+            assert_eq!(_erc20.balance_of(contract), 700);
+        }
+        #[ink::test]
+        fn claims_are_limited_by_reward_pool() {
+            let contract = set_contract_account();
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.set_staking_reward_rate(1).is_ok());
+            assert!(_erc20.fund_rewards(300).is_ok());
+            assert_eq!(_erc20.reward_pool(), 300);
+            assert!(_erc20.stake(100).is_ok());
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(200);
+            assert!(_erc20.claim_staking_rewards().is_ok());
+            assert_eq!(_erc20.balance_of(accounts.alice), 600 + 200);
+            assert_eq!(_erc20.reward_pool(), 100);
+
+            // accrued rewards outgrow the pool, nothing is minted
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(350);
+            assert_eq!(_erc20.claim_staking_rewards(), Err(Error::RewardPoolEmpty));
+            assert_eq!(_erc20.earned(accounts.alice), 150);
+            assert_eq!(_erc20.total_supply(), 1000);
+            assert_eq!(_erc20.balance_of(contract), 100 + 100);
+
+            assert!(_erc20.fund_rewards(50).is_ok());
+            assert!(_erc20.claim_staking_rewards().is_ok());
+            assert_eq!(_erc20.reward_pool(), 0);
+            assert_eq!(_erc20.balance_of(contract), 100);
+        }
+        #[ink::test]
+        fn paused_rewards_stop_accruing() {
+            let mut _erc20 = Erc20::new(1000);
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            assert!(_erc20.transfer(accounts.bob, 100).is_ok());
+            assert!(_erc20.set_staking_reward_rate(1).is_ok());
+            assert!(_erc20.stake(100).is_ok());
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert!(_erc20.stake(100).is_ok());
+            assert_eq!(_erc20.pause_rewards(accounts.bob), Err(Error::NotOwner));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert!(_erc20.pause_rewards(accounts.bob).is_ok());
+            assert_eq!(_erc20.earned(accounts.bob), 500);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(2000);
+            assert_eq!(_erc20.earned(accounts.bob), 500);
+            assert_eq!(_erc20.earned(accounts.alice), 1000);
+            assert_eq!(_erc20.staked_of(accounts.bob), 100);
+
+            assert!(_erc20.resume_rewards(accounts.bob).is_ok());
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(3000);
+            assert_eq!(_erc20.earned(accounts.bob), 1000);
+            assert_eq!(_erc20.earned(accounts.alice), 1500);
+        }
+        #[ink::test]
+        fn create_vesting_requires_owner() {
             let mut _erc20 = Erc20::new(10000);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            let res = _erc20.transfer(accounts.bob,12);
 
-            assert!(res.is_err());
-            assert_eq!(res,Err(Error::InsufficientBalance));
-        }     
+            assert_eq!(
+                _erc20.create_vesting(accounts.bob, 1000, 0, 1000),
+                Err(Error::NotOwner)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod property_tests {
+        use super::*;
+
+        /// Small linear congruential generator, good enough to drive random
+        /// operation sequences reproducibly.
+        struct Lcg(u64);
+
+        impl Lcg {
+            fn next(&mut self) -> u64 {
+                self.0 = self
+                    .0
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                self.0 >> 33
+            }
+
+            fn below(&mut self, bound: u64) -> u64 {
+                self.next() % bound
+            }
+        }
+
+        /// Applies random `transfer`, `transfer_from`, `approve`, `mint` and
+        /// `burn_and_revoke` calls, checking after each one that balances add up to
+        /// the total supply and that supply only moved by what was minted and burned.
+        fn check_supply_conservation(seed: u64) {
+            let default_accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let accounts = [
+                default_accounts.alice,
+                default_accounts.bob,
+                default_accounts.charlie,
+                default_accounts.django,
+                default_accounts.eve,
+            ];
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts[0]);
+            let initial_supply: Balance = 1_000_000;
+            let mut _erc20 = Erc20::new(initial_supply);
+            let mut rng = Lcg(seed);
+            let (mut minted, mut burned): (Balance, Balance) = (0, 0);
+
+            for _ in 0..300 {
+                let caller = accounts[rng.below(5) as usize];
+                let other = accounts[rng.below(5) as usize];
+                let third = accounts[rng.below(5) as usize];
+                let value = Balance::from(rng.below(50_000));
+                ink::env::test::set_caller::<ink::env::DefaultEnvironment>(caller);
+                match rng.below(5) {
+                    0 => {
+                        let _ = _erc20.transfer(other, value);
+                    }
+                    1 => {
+                        let _ = _erc20.transfer_from(other, third, value);
+                    }
+                    2 => {
+                        let _ = _erc20.approve(other, value);
+                    }
+                    3 => {
+                        // only the owner is a minter
+                        if _erc20.mint(other, value).is_ok() {
+                            minted += value;
+                        }
+                    }
+                    _ => {
+                        if _erc20.burn_and_revoke(value, other).is_ok() {
+                            burned += value;
+                        }
+                    }
+                }
+
+                let sum: Balance = accounts.iter().map(|account| _erc20.balance_of(*account)).sum();
+                assert_eq!(sum, _erc20.total_supply(), "balances don't add up to supply");
+                assert_eq!(
+                    _erc20.total_supply() + burned,
+                    initial_supply + minted,
+                    "supply changed by more than minted and burned"
+                );
+            }
+        }
+
+        #[test]
+        fn supply_is_conserved() {
+            for seed in 0..8 {
+                // a fresh off-chain environment per sequence
+                ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+                    check_supply_conservation(seed);
+                    Ok(())
+                })
+                .unwrap();
+            }
+        }
     }
 
     // #[cfg(feature = "e2e-tests")]